@@ -0,0 +1,49 @@
+#[macro_use]
+extern crate criterion;
+
+use criterion::{black_box, Criterion};
+
+use goban::pieces::bitboard::BitGoban;
+use goban::pieces::stones::Color;
+use goban::pieces::util::coord::one_to_2dim;
+use goban::rules::game::Game;
+
+/// The territories and the liberties of the chains on the final position of a game.
+pub fn bitboard_bench(_c: &mut Criterion) {
+    let game = Game::from_sgf(include_str!("../sgf/ShusakuvsInseki.sgf")).unwrap();
+    let goban = *game.goban();
+    let bit_goban = BitGoban::from(&goban);
+    Criterion::default()
+        .sample_size(100)
+        .bench_function("territories_goban", |b| {
+            b.iter(|| black_box(&goban).calculate_territories())
+        })
+        .bench_function("territories_bitboard", |b| {
+            b.iter(|| black_box(&bit_goban).calculate_territories())
+        })
+        .bench_function("chains_liberties_goban", |b| {
+            b.iter(|| {
+                black_box(&goban)
+                    .chains()
+                    .map(|chain| chain.number_of_liberties())
+                    .sum::<usize>()
+            })
+        })
+        .bench_function("chains_liberties_bitboard", |b| {
+            b.iter(|| {
+                let bit_goban = black_box(&bit_goban);
+                let mut liberties = 0;
+                let mut to_explore =
+                    bit_goban.stones(Color::Black) | bit_goban.stones(Color::White);
+                while let Some(first) = to_explore.first() {
+                    let chain = bit_goban.chain(one_to_2dim(bit_goban.size(), first));
+                    liberties += bit_goban.liberties(chain).count() as usize;
+                    to_explore = to_explore.and_not(chain);
+                }
+                liberties
+            })
+        });
+}
+
+criterion_group!(benches, bitboard_bench);
+criterion_main!(benches);
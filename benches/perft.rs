@@ -1,11 +1,14 @@
 #[macro_use]
 extern crate criterion;
 
-use criterion::Criterion;
+use std::hint;
+
+use criterion::{black_box, Criterion};
 use rand::prelude::{SliceRandom, ThreadRng};
 use rand::thread_rng;
 
-use goban::pieces::stones::Stone;
+use goban::pieces::goban::Goban;
+use goban::pieces::stones::{Color, Stone};
 use goban::rules::{CHINESE, GobanSizes, JAPANESE, Move, Rule};
 use goban::rules::game::Game;
 
@@ -29,6 +32,42 @@ pub fn perft(state: &Game, depth: u8) -> u64 {
     }
 }
 
+/// Perft on the goban alone, the suicides aren't counted and the ko isn't checked.
+pub fn goban_perft(goban: &mut Goban, color: Color, depth: u8) -> u64 {
+    if depth < 1 {
+        return 1;
+    }
+    let empties: Vec<_> = goban.get_empty_coords().collect();
+    let mut nodes = 0;
+    for point in empties {
+        let undo = goban.apply(point, color);
+        if !goban.get_chain_by_point(point).unwrap().is_dead() {
+            nodes += goban_perft(goban, !color, depth - 1);
+        }
+        goban.revert(undo);
+    }
+    nodes
+}
+
+/// Same as `goban_perft` with a copy of the goban by move.
+pub fn goban_perft_clone(goban: &Goban, color: Color, depth: u8) -> u64 {
+    if depth < 1 {
+        return 1;
+    }
+    goban
+        .get_empty_coords()
+        .map(|point| {
+            let mut child = *goban;
+            child.push(point, color);
+            if child.get_chain_by_point(point).unwrap().is_dead() {
+                0
+            } else {
+                goban_perft_clone(&child, !color, depth - 1)
+            }
+        })
+        .sum()
+}
+
 pub fn fast_play_random(state: &Game, thread_rng: &mut ThreadRng) -> Move {
     let mut v: Vec<_> = state.pseudo_legals().collect();
     v.shuffle(thread_rng);
@@ -435,5 +474,57 @@ pub fn game_play_bench(_c: &mut Criterion) {
         });
 }
 
-criterion_group!(benches, game_play_bench);
+/// The clones of the goban in the middle of a game, and the legal moves with the superko.
+pub fn goban_clone_bench(_c: &mut Criterion) {
+    let mut g = Game::new(GobanSizes::Nineteen, CHINESE);
+    for &m in MOVES_SGF.iter().take(150) {
+        g.play(m);
+    }
+    Criterion::default()
+        .sample_size(100)
+        .bench_function("goban_clone", |b| {
+            b.iter(|| {
+                let goban = *black_box(g.goban());
+                hint::black_box(&goban);
+            })
+        })
+        .bench_function("goban_clone_from", |b| {
+            let mut goban = Goban::default();
+            b.iter(|| goban.clone_from(black_box(g.goban())))
+        })
+        .bench_function("game_clone", |b| {
+            b.iter(|| {
+                let game = black_box(&g).clone();
+                hint::black_box(&game);
+            })
+        })
+        .bench_function("legals_superko", |b| {
+            b.iter(|| black_box(&g).legals().count())
+        });
+}
+
+/// The moves played and taken back in place against the copies of the goban, in the middle
+/// of a game.
+pub fn goban_apply_bench(_c: &mut Criterion) {
+    let mut g = Game::new(GobanSizes::Nineteen, CHINESE);
+    for &m in MOVES_SGF.iter().take(150) {
+        g.play(m);
+    }
+    Criterion::default()
+        .sample_size(10)
+        .bench_function("goban_perft_apply_2", |b| {
+            let mut goban = *g.goban();
+            b.iter(|| goban_perft(&mut goban, Color::Black, 2))
+        })
+        .bench_function("goban_perft_clone_2", |b| {
+            b.iter(|| goban_perft_clone(g.goban(), Color::Black, 2))
+        });
+}
+
+criterion_group!(
+    benches,
+    game_play_bench,
+    goban_clone_bench,
+    goban_apply_bench
+);
 criterion_main!(benches);
@@ -4,5 +4,7 @@ extern crate bitflags;
 #[macro_use]
 extern crate getset;
 
+pub mod notation;
 pub mod pieces;
+pub mod render;
 pub mod rules;
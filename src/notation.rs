@@ -0,0 +1,181 @@
+//! Module for converting coordinates and moves between the internal representation and the
+//! usual notations.
+//!
+//! Internally a `Coord` is (line, column) with (0, 0) in the top left corner.
+//! # Example
+//! ```
+//! use goban::notation::Notation;
+//! use goban::rules::Move;
+//!
+//! let size = (19, 19);
+//! assert_eq!(Notation::Gtp.format_coord(size, (15, 3)).unwrap(), "D4");
+//! assert_eq!(Notation::Sgf.format_coord(size, (15, 3)).unwrap(), "dp");
+//! assert_eq!(Notation::Japanese.format_coord(size, (15, 3)).unwrap(), "16-16");
+//! assert_eq!(Notation::Matrix.format_coord(size, (15, 3)).unwrap(), "16-4");
+//! assert_eq!(Notation::Gtp.format_coord(size, (19, 3)), None);
+//! assert_eq!(Notation::Gtp.parse_move(size, "q16"), Ok(Move::Play(3, 15)));
+//! assert_eq!(Notation::Sgf.parse_move(size, ""), Ok(Move::Pass));
+//! assert_eq!("dp".parse::<Move>(), Ok(Move::Play(15, 3)));
+//! ```
+
+use crate::pieces::stones::Color;
+use crate::pieces::util::coord::{is_coord_valid, Coord, Size};
+use crate::pieces::Nat;
+use crate::rules::Move;
+
+/// Letters of the columns in GTP, the I is skipped.
+const GTP_COLUMNS: &[u8] = b"ABCDEFGHJKLMNOPQRSTUVWXYZ";
+
+const KANJI_DIGITS: [char; 9] = ['一', '二', '三', '四', '五', '六', '七', '八', '九'];
+
+/// The coordinate systems supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Notation {
+    /// GTP vertices like "D4", the letter is the column without I, the number is the line
+    /// counted from the bottom. Pass is "pass".
+    Gtp,
+    /// SGF points like "dp", the column letter then the line letter counted from the top.
+    /// Pass is "" ("tt" is also read as a pass).
+    Sgf,
+    /// Japanese coordinates like "16-16" (or "16-十六"), the column counted from the right then
+    /// the line counted from the top.
+    Japanese,
+    /// Matrix coordinates like "16-4", the line then the column counted from 1 in the top left.
+    Matrix,
+}
+
+impl Notation {
+    /// Formats the coordinate. The size of the goban is needed because some notations count
+    /// from the bottom or the right. None if the coordinate isn't in the goban, or if the
+    /// notation has no letter for its column or its line.
+    pub fn format_coord(self, size: Size, coord: Coord) -> Option<String> {
+        if !is_coord_valid(size, coord) {
+            return None;
+        }
+        let ((height, width), (line, column)) = (size, coord);
+        let formatted = match self {
+            Notation::Gtp => {
+                let letter = GTP_COLUMNS.get(column as usize)?;
+                format!("{}{}", *letter as char, height - line)
+            }
+            Notation::Sgf => [sgf_letter(column)?, sgf_letter(line)?].iter().collect(),
+            Notation::Japanese => format!("{}-{}", width - column, line + 1),
+            Notation::Matrix => format!("{}-{}", line + 1, column + 1),
+        };
+        Some(formatted)
+    }
+
+    /// Parses a coordinate, the coordinate must be in the goban.
+    pub fn parse_coord(self, size: Size, s: &str) -> Result<Coord, String> {
+        let (height, width) = size;
+        let err = || format!("Invalid {self:?} coordinate: {s}");
+        let s = s.trim();
+        let coord = match self {
+            Notation::Gtp => {
+                let mut chars = s.chars();
+                let column = chars.next().ok_or_else(err)?.to_ascii_uppercase();
+                let column = GTP_COLUMNS
+                    .iter()
+                    .position(|&c| c as char == column)
+                    .ok_or_else(err)?;
+                let line = parse_number(chars.as_str()).ok_or_else(err)?;
+                if line == 0 || line > height as u32 {
+                    return Err(err());
+                }
+                (height - line as Nat, column as Nat)
+            }
+            Notation::Sgf => match s.as_bytes() {
+                &[column, line] if column.is_ascii_lowercase() && line.is_ascii_lowercase() => {
+                    (line - b'a', column - b'a')
+                }
+                _ => return Err(err()),
+            },
+            Notation::Japanese | Notation::Matrix => {
+                let (first, second) = s.split_once('-').ok_or_else(err)?;
+                let first = parse_number(first).ok_or_else(err)?;
+                let second = parse_number(second).ok_or_else(err)?;
+                if first == 0 || second == 0 {
+                    return Err(err());
+                }
+                if self == Notation::Japanese {
+                    if first > width as u32 {
+                        return Err(err());
+                    }
+                    ((second - 1) as Nat, width - first as Nat)
+                } else {
+                    ((first - 1) as Nat, (second - 1) as Nat)
+                }
+            }
+        };
+        if is_coord_valid(size, coord) {
+            Ok(coord)
+        } else {
+            Err(err())
+        }
+    }
+
+    /// Formats a move, a resignation is formatted like a SGF result, ex "W+R" if black resigned.
+    /// None if the move is played outside the goban, see `format_coord`.
+    pub fn format_move(self, size: Size, m: Move) -> Option<String> {
+        match m {
+            Move::Play(line, column) => self.format_coord(size, (line, column)),
+            Move::Pass if self == Notation::Sgf => Some(String::new()),
+            Move::Pass => Some("pass".to_string()),
+            Move::Resign(winner) => Some(format!("{}+R", winner_letter(winner))),
+        }
+    }
+
+    /// Parses a move, "pass" is accepted in every notation. The resignations are "B+R" or "W+R"
+    /// because the winner is needed.
+    pub fn parse_move(self, size: Size, s: &str) -> Result<Move, String> {
+        let s = s.trim();
+        match s.to_lowercase().as_str() {
+            "pass" => return Ok(Move::Pass),
+            "" | "tt" if self == Notation::Sgf => return Ok(Move::Pass),
+            "b+r" | "b+resign" => return Ok(Move::Resign(Color::Black)),
+            "w+r" | "w+resign" => return Ok(Move::Resign(Color::White)),
+            _ => (),
+        }
+        self.parse_coord(size, s).map(Move::from)
+    }
+}
+
+/// Returns the GTP letter of the column, the I is skipped.
+pub(crate) fn gtp_column(column: Nat) -> char {
+    GTP_COLUMNS[column as usize] as char
+}
+
+/// Returns the SGF letter of the line or the column. None after the z.
+fn sgf_letter(index: Nat) -> Option<char> {
+    (index < 26).then(|| (b'a' + index) as char)
+}
+
+fn winner_letter(color: Color) -> char {
+    match color {
+        Color::Black => 'B',
+        Color::White => 'W',
+    }
+}
+
+/// Parses a number in arabic digits or in kanji up to 99.
+fn parse_number(s: &str) -> Option<u32> {
+    let s = s.trim();
+    if let Ok(n) = s.parse::<u32>() {
+        return Some(n);
+    }
+    let kanji_digit = |c: char| {
+        KANJI_DIGITS
+            .iter()
+            .position(|&k| k == c)
+            .map(|d| d as u32 + 1)
+    };
+    let chars: Vec<char> = s.chars().collect();
+    match chars[..] {
+        ['十'] => Some(10),
+        [c] => kanji_digit(c),
+        ['十', unit] => kanji_digit(unit).map(|u| 10 + u),
+        [tens, '十'] => kanji_digit(tens).map(|t| 10 * t),
+        [tens, '十', unit] => Some(10 * kanji_digit(tens)? + kanji_digit(unit)?),
+        _ => None,
+    }
+}
@@ -0,0 +1,732 @@
+//! Module with a goban stored in bitboards, one bit by point for the black stones and one for
+//! the white stones. The chains, the liberties, the captures and the territories are flood
+//! fills done with shifts and masks on the packed words, like the `Liberties` of the chains.
+//!
+//! `BitGoban` has the same methods as `Goban` for the stones, and it can be converted from and
+//! to a `Goban`. It's a `Board`, so a game can be played on it with `Game::with_board`.
+//! # Example
+//! ```
+//! use goban::pieces::bitboard::BitGoban;
+//! use goban::pieces::stones::Color;
+//!
+//! let mut goban = BitGoban::new((9, 9));
+//! goban.push((0, 1), Color::Black).push((1, 0), Color::Black);
+//! goban.push((0, 0), Color::White);
+//! let undo = goban.apply((5, 5), Color::Black);
+//! assert_eq!(undo.captured_stones(), 0);
+//! assert!(!goban.has_liberties((0, 0)));
+//! assert_eq!(goban.chain((0, 1)).count(), 1);
+//! assert_eq!(goban.liberties(goban.chain((5, 5))).count(), 4);
+//! assert_eq!(goban.calculate_territories(), (77, 0));
+//! ```
+
+use std::fmt::{Display, Error, Formatter};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
+use std::str::FromStr;
+
+use crate::pieces::board::Board;
+use crate::pieces::goban::Goban;
+use crate::pieces::stones::{Color, MaybeColor, Point, Stone, EMPTY};
+use crate::pieces::util::coord::{
+    is_coord_valid, one_to_2dim, two_to_1dim, valid_coords, Coord, Size,
+};
+use crate::pieces::zobrist::index_zobrist;
+use crate::pieces::BoardIdx;
+use crate::rules::PlayError;
+
+const WORDS: usize = 361 / u64::BITS as usize + 1;
+const BITS: usize = u64::BITS as usize;
+
+/// A set of points of the goban, in a RowMajor Policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Bitboard([u64; WORDS]);
+
+impl Bitboard {
+    /// The set without points.
+    pub const EMPTY: Bitboard = Bitboard([0; WORDS]);
+
+    #[inline(always)]
+    pub fn get(&self, index: BoardIdx) -> bool {
+        self.0[index / BITS] & (1 << (index % BITS)) != 0
+    }
+
+    #[inline(always)]
+    pub fn set(&mut self, index: BoardIdx) {
+        self.0[index / BITS] |= 1 << (index % BITS);
+    }
+
+    #[inline(always)]
+    pub fn clear(&mut self, index: BoardIdx) {
+        self.0[index / BITS] &= !(1 << (index % BITS));
+    }
+
+    /// Number of points in the set.
+    #[inline]
+    pub fn count(&self) -> u32 {
+        self.0.iter().map(|word| word.count_ones()).sum()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|&word| word == 0)
+    }
+
+    /// The smallest index of the set.
+    #[inline]
+    pub fn first(&self) -> Option<BoardIdx> {
+        self.0
+            .iter()
+            .position(|&word| word != 0)
+            .map(|word| word * BITS + self.0[word].trailing_zeros() as usize)
+    }
+
+    /// The points of `self` who aren't in `other`.
+    #[inline(always)]
+    pub fn and_not(self, other: Bitboard) -> Bitboard {
+        let mut res = self;
+        res.0.iter_mut().zip(other.0).for_each(|(x, o)| *x &= !o);
+        res
+    }
+
+    /// Iterates over the indexes of the set, in increasing order.
+    pub fn iter(self) -> impl Iterator<Item = BoardIdx> {
+        self.0.into_iter().enumerate().flat_map(|(ix, word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    None
+                } else {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    Some(ix * BITS + bit)
+                }
+            })
+        })
+    }
+
+    /// Moves the points `n` indexes up, `n` must be between 1 and 63.
+    #[inline(always)]
+    fn shift_up(self, n: usize) -> Bitboard {
+        debug_assert!((1..BITS).contains(&n));
+        Bitboard(std::array::from_fn(|i| {
+            let carry = if i == 0 {
+                0
+            } else {
+                self.0[i - 1] >> (BITS - n)
+            };
+            self.0[i] << n | carry
+        }))
+    }
+
+    /// Moves the points `n` indexes down, `n` must be between 1 and 63.
+    #[inline(always)]
+    fn shift_down(self, n: usize) -> Bitboard {
+        debug_assert!((1..BITS).contains(&n));
+        Bitboard(std::array::from_fn(|i| {
+            let carry = self.0.get(i + 1).map_or(0, |word| word << (BITS - n));
+            self.0[i] >> n | carry
+        }))
+    }
+}
+
+macro_rules! bit_op {
+    ($op: ident, $fn: ident, $op_assign: ident, $fn_assign: ident) => {
+        impl $op for Bitboard {
+            type Output = Bitboard;
+
+            #[inline(always)]
+            fn $fn(mut self, rhs: Bitboard) -> Bitboard {
+                self.$fn_assign(rhs);
+                self
+            }
+        }
+
+        impl $op_assign for Bitboard {
+            #[inline(always)]
+            fn $fn_assign(&mut self, rhs: Bitboard) {
+                self.0
+                    .iter_mut()
+                    .zip(rhs.0)
+                    .for_each(|(x, o)| x.$fn_assign(o));
+            }
+        }
+    };
+}
+
+bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+bit_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+/// The masks of a goban size, to keep the shifts inside the goban.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Geometry {
+    size: Size,
+    board: Bitboard,
+    not_first_column: Bitboard,
+    not_last_column: Bitboard,
+}
+
+impl Geometry {
+    fn new(size: Size) -> Self {
+        let mut geometry = Geometry {
+            size,
+            board: Bitboard::EMPTY,
+            not_first_column: Bitboard::EMPTY,
+            not_last_column: Bitboard::EMPTY,
+        };
+        for index in 0..size.0 as usize * size.1 as usize {
+            geometry.board.set(index);
+            let column = index % size.1 as usize;
+            if column != 0 {
+                geometry.not_first_column.set(index);
+            }
+            if column != size.1 as usize - 1 {
+                geometry.not_last_column.set(index);
+            }
+        }
+        geometry
+    }
+
+    /// The points next to the set, with the set.
+    #[inline(always)]
+    fn dilate(&self, set: Bitboard) -> Bitboard {
+        let width = self.size.1 as usize;
+        (set | set.shift_up(width)
+            | set.shift_down(width)
+            | (set & self.not_last_column).shift_up(1)
+            | (set & self.not_first_column).shift_down(1))
+            & self.board
+    }
+
+    /// The points next to the set, without the set.
+    #[inline(always)]
+    fn neighbors(&self, set: Bitboard) -> Bitboard {
+        self.dilate(set).and_not(set)
+    }
+
+    /// The points of `within` connected to the seed.
+    #[inline]
+    fn fill(&self, seed: Bitboard, within: Bitboard) -> Bitboard {
+        let mut filled = seed & within;
+        loop {
+            let next = self.dilate(filled) & within;
+            if next == filled {
+                return filled;
+            }
+            filled = next;
+        }
+    }
+}
+
+/// A goban stored in bitboards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitGoban {
+    geometry: Geometry,
+    black: Bitboard,
+    white: Bitboard,
+    zobrist_hash: u64,
+}
+
+/// What a move played by `BitGoban::apply` changed, to take it back with `BitGoban::revert`.
+#[derive(Debug, Clone, Copy)]
+#[must_use = "The move can only be taken back with the undo record"]
+pub struct BitUndo {
+    point: BoardIdx,
+    color: Color,
+    captured: Bitboard,
+    hash: u64,
+}
+
+impl BitUndo {
+    /// The number of stones captured by the move.
+    #[inline]
+    pub fn captured_stones(&self) -> u32 {
+        self.captured.count()
+    }
+
+    /// The stones captured by the move.
+    #[inline]
+    pub fn captured(&self) -> Bitboard {
+        self.captured
+    }
+
+    /// The hash of the goban before the move.
+    #[inline]
+    pub fn previous_hash(&self) -> u64 {
+        self.hash
+    }
+}
+
+impl BitGoban {
+    /// Creates a BitGoban.
+    ///
+    /// # Panics
+    /// if a side is bigger than 19, see `try_new` for a checked version.
+    pub fn new((height, width): Size) -> Self {
+        assert!(
+            height <= 19 && width <= 19,
+            "The goban {height}x{width} is too big"
+        );
+        BitGoban {
+            geometry: Geometry::new((height, width)),
+            black: Bitboard::EMPTY,
+            white: Bitboard::EMPTY,
+            zobrist_hash: 0,
+        }
+    }
+
+    /// Creates a BitGoban, the sides must be between 1 and 19.
+    ///
+    /// # Errors
+    /// InvalidSize if a side is 0 or bigger than 19.
+    pub fn try_new((height, width): Size) -> Result<Self, PlayError> {
+        if (1..=19).contains(&height) && (1..=19).contains(&width) {
+            Ok(BitGoban::new((height, width)))
+        } else {
+            Err(PlayError::InvalidSize)
+        }
+    }
+
+    #[inline]
+    pub fn size(&self) -> Size {
+        self.geometry.size
+    }
+
+    /// The same hash as the `Goban` with the same stones.
+    #[inline]
+    pub fn zobrist_hash(&self) -> u64 {
+        self.zobrist_hash
+    }
+
+    /// The stones of a color.
+    #[inline]
+    pub fn stones(&self, color: Color) -> Bitboard {
+        match color {
+            Color::Black => self.black,
+            Color::White => self.white,
+        }
+    }
+
+    /// The empty points.
+    #[inline]
+    pub fn empty(&self) -> Bitboard {
+        self.geometry.board.and_not(self.black | self.white)
+    }
+
+    #[inline(always)]
+    fn stones_mut(&mut self, color: Color) -> &mut Bitboard {
+        match color {
+            Color::Black => &mut self.black,
+            Color::White => &mut self.white,
+        }
+    }
+
+    #[inline(always)]
+    fn index(&self, point: Coord) -> BoardIdx {
+        two_to_1dim(self.size(), point)
+    }
+
+    /// Put a stone in the goban, the (0,0) point is in the top left. Like `Goban::push`
+    /// the captures aren't removed.
+    ///
+    /// # Panics
+    /// if the point is out of bounds
+    pub fn push(&mut self, point: Coord, color: Color) -> &mut Self {
+        assert!(
+            is_coord_valid(self.size(), point),
+            "Coordinate {point:?} out of bounds"
+        );
+        let index = self.index(point);
+        debug_assert!(
+            self.get_color(point).is_none(),
+            "The point {point:?} isn't empty"
+        );
+        self.stones_mut(color).set(index);
+        self.zobrist_hash ^= index_zobrist(index, color);
+        self
+    }
+
+    /// Put a stone in the goban, but checks the point first.
+    ///
+    /// # Errors
+    /// OutOfBounds if the point is outside the goban, PointNotEmpty if there is already a stone.
+    pub fn try_push(&mut self, point: Coord, color: Color) -> Result<&mut Self, PlayError> {
+        if !is_coord_valid(self.size(), point) {
+            Err(PlayError::OutOfBounds)
+        } else if self.get_color(point).is_some() {
+            Err(PlayError::PointNotEmpty)
+        } else {
+            Ok(self.push(point, color))
+        }
+    }
+
+    /// Helper function to put a stone.
+    #[inline]
+    pub fn push_stone(&mut self, stone: Stone) -> &mut Self {
+        self.push(stone.coord, stone.color)
+    }
+
+    /// Put many stones.
+    #[inline]
+    pub fn push_many(&mut self, points: &[Coord], value: Color) {
+        points.iter().for_each(|&point| {
+            self.push(point, value);
+        })
+    }
+
+    /// Plays a stone and removes the chains of the other color it captures, the move is taken
+    /// back by `revert`. Like `Goban::apply` a stone without liberties stays on the goban.
+    pub fn apply(&mut self, point: Coord, color: Color) -> BitUndo {
+        let hash = self.zobrist_hash;
+        self.push(point, color);
+        let index = self.index(point);
+        let mut stone = Bitboard::EMPTY;
+        stone.set(index);
+
+        let empty = self.empty();
+        let opponents = self.stones(!color);
+        let mut captured = Bitboard::EMPTY;
+        let mut to_check = self.geometry.neighbors(stone) & opponents;
+        while let Some(first) = to_check.first() {
+            let mut seed = Bitboard::EMPTY;
+            seed.set(first);
+            let chain = self.geometry.fill(seed, opponents);
+            if (self.geometry.neighbors(chain) & empty).is_empty() {
+                captured |= chain;
+            }
+            to_check = to_check.and_not(chain);
+        }
+        self.remove_stones(captured, !color);
+        BitUndo {
+            point: index,
+            color,
+            captured,
+            hash,
+        }
+    }
+
+    /// Takes back the move of the undo record, the moves must be taken back in the reverse
+    /// order they were applied.
+    pub fn revert(&mut self, undo: BitUndo) {
+        self.stones_mut(undo.color).clear(undo.point);
+        *self.stones_mut(!undo.color) |= undo.captured;
+        self.zobrist_hash = undo.hash;
+    }
+
+    /// Removes stones of a color from the goban.
+    pub fn remove_stones(&mut self, stones: Bitboard, color: Color) {
+        for index in stones.iter() {
+            self.zobrist_hash ^= index_zobrist(index, color);
+        }
+        let own = self.stones_mut(color);
+        *own = own.and_not(stones);
+    }
+
+    /// Returns the points connected to the point with the same color, works for the empty
+    /// points too.
+    pub fn chain(&self, point: Coord) -> Bitboard {
+        let within = match self.get_color(point) {
+            Some(color) => self.stones(color),
+            EMPTY => self.empty(),
+        };
+        let mut seed = Bitboard::EMPTY;
+        seed.set(self.index(point));
+        self.geometry.fill(seed, within)
+    }
+
+    /// Returns the liberties of a set of stones.
+    #[inline]
+    pub fn liberties(&self, stones: Bitboard) -> Bitboard {
+        self.geometry.neighbors(stones) & self.empty()
+    }
+
+    /// Returns the points next to a set of points.
+    #[inline]
+    pub fn neighbors(&self, points: Bitboard) -> Bitboard {
+        self.geometry.neighbors(points)
+    }
+
+    /// Returns the points of `within` connected to the seed.
+    #[inline]
+    pub fn fill(&self, seed: Bitboard, within: Bitboard) -> Bitboard {
+        self.geometry.fill(seed, within)
+    }
+
+    /// Returns the chains without liberties of a color.
+    pub fn dead_stones(&self, color: Color) -> Bitboard {
+        let own = self.stones(color);
+        let alive = self
+            .geometry
+            .fill(self.geometry.neighbors(self.empty()) & own, own);
+        own.and_not(alive)
+    }
+
+    #[inline]
+    pub fn get_color(&self, point: Coord) -> MaybeColor {
+        let index = self.index(point);
+        if self.black.get(index) {
+            Some(Color::Black)
+        } else if self.white.get(index) {
+            Some(Color::White)
+        } else {
+            EMPTY
+        }
+    }
+
+    #[inline]
+    pub fn get_point(&self, coord: Coord) -> Point {
+        Point {
+            coord,
+            color: self.get_color(coord),
+        }
+    }
+
+    /// Returns the stones of the goban, the black stones first.
+    pub fn get_stones(&self) -> impl Iterator<Item = Stone> + '_ {
+        let size = self.size();
+        let stones = |color| {
+            self.stones(color).iter().map(move |index| Stone {
+                coord: one_to_2dim(size, index),
+                color,
+            })
+        };
+        stones(Color::Black).chain(stones(Color::White))
+    }
+
+    pub fn get_empty_coords(&self) -> impl Iterator<Item = Coord> {
+        let size = self.size();
+        self.empty()
+            .iter()
+            .map(move |index| one_to_2dim(size, index))
+    }
+
+    /// Returns the "empty" stones connected to the stone.
+    pub fn get_liberties(&self, coord: Coord) -> impl Iterator<Item = Coord> {
+        let mut point = Bitboard::EMPTY;
+        point.set(self.index(coord));
+        let size = self.size();
+        self.liberties(point)
+            .iter()
+            .map(move |index| one_to_2dim(size, index))
+    }
+
+    /// Returns true if the stone has liberties.
+    #[inline]
+    pub fn has_liberties(&self, coord: Coord) -> bool {
+        let mut point = Bitboard::EMPTY;
+        point.set(self.index(coord));
+        !self.liberties(point).is_empty()
+    }
+
+    /// Returns the number of stones (black, white).
+    #[inline]
+    pub fn number_of_stones(&self) -> (u32, u32) {
+        (self.black.count(), self.white.count())
+    }
+
+    /// Returns the goban in a vector with a RowMajor Policy.
+    pub fn to_vec(&self) -> Vec<MaybeColor> {
+        let (height, width) = self.size();
+        (0..height)
+            .flat_map(|line| (0..width).map(move |column| (line, column)))
+            .map(|point| self.get_color(point))
+            .collect()
+    }
+
+    /// Returns the empty points surrounded by only one color (black, white), like
+    /// `Goban::get_territories`.
+    pub fn territories(&self) -> (Bitboard, Bitboard) {
+        let empty = self.empty();
+        let mut black_territory = Bitboard::EMPTY;
+        let mut white_territory = Bitboard::EMPTY;
+        let mut to_explore = empty;
+        while let Some(first) = to_explore.first() {
+            let mut seed = Bitboard::EMPTY;
+            seed.set(first);
+            let region = self.geometry.fill(seed, empty);
+            let border = self.geometry.neighbors(region);
+            match (
+                (border & self.black).is_empty(),
+                (border & self.white).is_empty(),
+            ) {
+                (false, true) => black_territory |= region,
+                (true, false) => white_territory |= region,
+                _ => {}
+            }
+            to_explore = to_explore.and_not(region);
+        }
+        (black_territory, white_territory)
+    }
+
+    /// Returns the number of points of the territories (black, white), like
+    /// `Goban::calculate_territories`.
+    #[inline]
+    pub fn calculate_territories(&self) -> (usize, usize) {
+        let (black, white) = self.territories();
+        (black.count() as usize, white.count() as usize)
+    }
+
+    /// Get a string for printing the goban, the (0,0) point is in the top left corner.
+    pub fn pretty_string(&self) -> String {
+        Goban::from(self).pretty_string()
+    }
+}
+
+impl Board for BitGoban {
+    #[inline]
+    fn new(size: Size) -> Self {
+        BitGoban::new(size)
+    }
+
+    #[inline]
+    fn size(&self) -> Size {
+        BitGoban::size(self)
+    }
+
+    #[inline]
+    fn zobrist_hash(&self) -> u64 {
+        BitGoban::zobrist_hash(self)
+    }
+
+    #[inline]
+    fn get_color(&self, point: Coord) -> MaybeColor {
+        BitGoban::get_color(self, point)
+    }
+
+    #[inline]
+    fn get_empty_coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        BitGoban::get_empty_coords(self)
+    }
+
+    #[inline]
+    fn number_of_stones(&self) -> (u32, u32) {
+        BitGoban::number_of_stones(self)
+    }
+
+    #[inline]
+    fn to_vec(&self) -> Vec<MaybeColor> {
+        BitGoban::to_vec(self)
+    }
+
+    #[inline]
+    fn push(&mut self, point: Coord, color: Color) -> &mut Self {
+        BitGoban::push(self, point, color)
+    }
+
+    fn push_with_captures(
+        &mut self,
+        point: Coord,
+        color: Color,
+        captured: Option<&mut Vec<Vec<Coord>>>,
+    ) -> (u32, Option<Coord>) {
+        let undo = self.apply(point, color);
+        let size = self.size();
+        if let Some(captured) = captured {
+            let mut to_split = undo.captured;
+            while let Some(first) = to_split.first() {
+                let mut seed = Bitboard::EMPTY;
+                seed.set(first);
+                let chain = self.geometry.fill(seed, to_split);
+                captured.push(chain.iter().map(|index| one_to_2dim(size, index)).collect());
+                to_split = to_split.and_not(chain);
+            }
+        }
+        let ko_point = match undo.captured_stones() {
+            1 => undo.captured.first().map(|index| one_to_2dim(size, index)),
+            _ => None,
+        };
+        (undo.captured_stones(), ko_point)
+    }
+
+    #[inline]
+    fn hash_after(&self, point: Coord, color: Color) -> u64 {
+        let mut goban = *self;
+        let _undo = goban.apply(point, color);
+        goban.zobrist_hash()
+    }
+
+    #[inline]
+    fn chain_id(&self, point: Coord) -> Option<usize> {
+        self.get_color(point)
+            .and_then(|_| self.chain(point).first())
+    }
+
+    fn chain_stones(&self, point: Coord) -> Vec<Coord> {
+        if self.get_color(point).is_none() {
+            return vec![];
+        }
+        let size = self.size();
+        self.chain(point)
+            .iter()
+            .map(|index| one_to_2dim(size, index))
+            .collect()
+    }
+
+    #[inline]
+    fn chain_liberties(&self, point: Coord) -> usize {
+        if self.get_color(point).is_none() {
+            0
+        } else {
+            self.liberties(self.chain(point)).count() as usize
+        }
+    }
+
+    fn neighbor_chains(&self, point: Coord) -> impl Iterator<Item = (Color, usize)> + '_ {
+        valid_coords(point, self.size())
+            .into_iter()
+            .filter_map(move |p| {
+                self.get_color(p)
+                    .map(|color| (color, self.chain_liberties(p)))
+            })
+    }
+
+    #[inline]
+    fn has_liberties(&self, point: Coord) -> bool {
+        BitGoban::has_liberties(self, point)
+    }
+
+    #[inline]
+    fn calculate_territories(&self) -> (usize, usize) {
+        BitGoban::calculate_territories(self)
+    }
+}
+
+impl From<&Goban> for BitGoban {
+    fn from(goban: &Goban) -> Self {
+        let mut bit_goban = BitGoban::new(goban.size());
+        for stone in goban.get_stones() {
+            bit_goban.push_stone(stone);
+        }
+        bit_goban
+    }
+}
+
+impl From<&BitGoban> for Goban {
+    fn from(bit_goban: &BitGoban) -> Self {
+        let mut goban = Goban::new(bit_goban.size());
+        for stone in bit_goban.get_stones() {
+            goban.push_stone(stone);
+        }
+        goban
+    }
+}
+
+impl FromStr for BitGoban {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(BitGoban::from(&s.parse::<Goban>()?))
+    }
+}
+
+impl Display for BitGoban {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{}", self.pretty_string())
+    }
+}
+
+impl Default for BitGoban {
+    fn default() -> Self {
+        BitGoban::new((19, 19))
+    }
+}
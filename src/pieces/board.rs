@@ -0,0 +1,209 @@
+//! Module with the trait of the boards used by the games, the rules only use the board
+//! through it. `Goban` is the board by default, see `Game::with_board` for the others.
+//! The analyses only written for the goban, like the ownership map of `Game::score_report`,
+//! work on the copy of the board given by `Board::to_goban`. The games read from SGF or from
+//! a diagram are on a `Goban`, `Game::convert` moves them on another board.
+//! # Example
+//! ```
+//! use goban::pieces::board::Board;
+//! use goban::pieces::goban::Goban;
+//! use goban::pieces::stones::Color;
+//!
+//! fn atari<B: Board>(board: &B, point: (u8, u8)) -> bool {
+//!     board.get_color(point).is_some() && board.chain_liberties(point) == 1
+//! }
+//!
+//! let mut goban = Goban::new((9, 9));
+//! goban.push((0, 0), Color::White).push((0, 1), Color::Black);
+//! assert!(atari(&goban, (0, 0)));
+//! let captures = Board::push_with_captures(&mut goban, (1, 0), Color::Black, None);
+//! assert_eq!(captures, (1, Some((0, 0))));
+//! ```
+
+use std::fmt::{Debug, Display};
+
+use crate::pieces::goban::Goban;
+use crate::pieces::stones::{Color, MaybeColor};
+use crate::pieces::util::coord::{one_to_2dim, two_to_1dim, Coord, Size};
+
+/// The operations of a board needed by the rules.
+pub trait Board: Clone + Debug + Display + PartialEq {
+    /// Creates an empty board.
+    fn new(size: Size) -> Self;
+
+    fn size(&self) -> Size;
+
+    /// The zobrist hash of the stones, the ko and the super ko are checked with it.
+    fn zobrist_hash(&self) -> u64;
+
+    fn get_color(&self, point: Coord) -> MaybeColor;
+
+    /// The empty points in a RowMajor Policy.
+    fn get_empty_coords(&self) -> impl Iterator<Item = Coord> + '_;
+
+    /// Returns the number of stones (black, white).
+    fn number_of_stones(&self) -> (u32, u32);
+
+    /// Returns the colors in a RowMajor Policy, the entries after the last point are ignored.
+    fn to_vec(&self) -> Vec<MaybeColor>;
+
+    /// Puts a stone without removing the captures, for the handicap and the setup stones.
+    fn push(&mut self, point: Coord, color: Color) -> &mut Self;
+
+    /// Plays a stone and removes the chains of the other color without liberties. Returns
+    /// the number of captured stones, and the ko point if only one stone was captured.
+    /// The stones of each captured chain are added to `captured` if asked.
+    /// Like `push` the point isn't checked and a suicide stays on the board, the suicides are
+    /// checked by `Game::try_play` with the rule of the game.
+    fn push_with_captures(
+        &mut self,
+        point: Coord,
+        color: Color,
+        captured: Option<&mut Vec<Vec<Coord>>>,
+    ) -> (u32, Option<Coord>);
+
+    /// Returns the hash of the board if the stone was played, with the captures.
+    fn hash_after(&self, point: Coord, color: Color) -> u64;
+
+    /// An id shared by the stones of the chain at the point, until the board changes. None
+    /// for an empty point.
+    fn chain_id(&self, point: Coord) -> Option<usize>;
+
+    /// The stones of the chain at the point.
+    fn chain_stones(&self, point: Coord) -> Vec<Coord>;
+
+    /// The number of liberties of the chain at the point, 0 for an empty point.
+    fn chain_liberties(&self, point: Coord) -> usize;
+
+    /// The color and the number of liberties of the chains next to the point, a chain can
+    /// be there several times.
+    fn neighbor_chains(&self, point: Coord) -> impl Iterator<Item = (Color, usize)> + '_;
+
+    /// Returns true if the point has an empty neighbor.
+    fn has_liberties(&self, point: Coord) -> bool;
+
+    /// Returns the number of empty points surrounded by only one color (black, white).
+    fn calculate_territories(&self) -> (usize, usize);
+
+    /// Returns a goban with the same stones.
+    fn to_goban(&self) -> Goban {
+        let size = self.size();
+        let mut goban = Goban::new(size);
+        let points = self
+            .to_vec()
+            .into_iter()
+            .take(size.0 as usize * size.1 as usize);
+        for (index, color) in points.enumerate() {
+            if let Some(color) = color {
+                goban.push(one_to_2dim(size, index), color);
+            }
+        }
+        goban
+    }
+}
+
+impl Board for Goban {
+    #[inline]
+    fn new(size: Size) -> Self {
+        Goban::new(size)
+    }
+
+    #[inline]
+    fn size(&self) -> Size {
+        Goban::size(self)
+    }
+
+    #[inline]
+    fn zobrist_hash(&self) -> u64 {
+        Goban::zobrist_hash(self)
+    }
+
+    #[inline]
+    fn get_color(&self, point: Coord) -> MaybeColor {
+        Goban::get_color(self, point)
+    }
+
+    #[inline]
+    fn get_empty_coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        Goban::get_empty_coords(self)
+    }
+
+    #[inline]
+    fn number_of_stones(&self) -> (u32, u32) {
+        Goban::number_of_stones(self)
+    }
+
+    #[inline]
+    fn to_vec(&self) -> Vec<MaybeColor> {
+        Goban::to_vec(self)
+    }
+
+    #[inline]
+    fn push(&mut self, point: Coord, color: Color) -> &mut Self {
+        Goban::push(self, point, color)
+    }
+
+    #[inline]
+    fn push_with_captures(
+        &mut self,
+        point: Coord,
+        color: Color,
+        captured: Option<&mut Vec<Vec<Coord>>>,
+    ) -> (u32, Option<Coord>) {
+        let (dead_chains, added_chain) = self.push_wth_feedback(point, color);
+        if let Some(captured) = captured {
+            captured.extend(
+                dead_chains
+                    .iter()
+                    .map(|&chain_idx| self.get_chain_coords(chain_idx)),
+            );
+        }
+        // The rule isn't known by the board, the suicide isn't removed like on a `BitGoban`.
+        let (prisoners, ko_point) =
+            self.remove_captured_stones_aux(color, false, (0, 0), &dead_chains, added_chain);
+        (prisoners.0 + prisoners.1, ko_point)
+    }
+
+    #[inline]
+    fn hash_after(&self, point: Coord, color: Color) -> u64 {
+        Goban::hash_after(self, point, color)
+    }
+
+    #[inline]
+    fn chain_id(&self, point: Coord) -> Option<usize> {
+        self.chain_idx_at(two_to_1dim(Goban::size(self), point))
+    }
+
+    #[inline]
+    fn chain_stones(&self, point: Coord) -> Vec<Coord> {
+        self.chain_id(point)
+            .map_or_else(Vec::new, |chain_idx| self.get_chain_coords(chain_idx))
+    }
+
+    #[inline]
+    fn chain_liberties(&self, point: Coord) -> usize {
+        self.get_chain_by_point(point)
+            .map_or(0, |chain| chain.number_of_liberties())
+    }
+
+    #[inline]
+    fn neighbor_chains(&self, point: Coord) -> impl Iterator<Item = (Color, usize)> + '_ {
+        self.get_neighbors_chains(point)
+            .map(|chain| (chain.color, chain.number_of_liberties()))
+    }
+
+    #[inline]
+    fn has_liberties(&self, point: Coord) -> bool {
+        Goban::has_liberties(self, point)
+    }
+
+    #[inline]
+    fn calculate_territories(&self) -> (usize, usize) {
+        Goban::calculate_territories(self)
+    }
+
+    #[inline]
+    fn to_goban(&self) -> Goban {
+        *self
+    }
+}
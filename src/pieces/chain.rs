@@ -1,5 +1,3 @@
-use std::ops::BitOrAssign;
-
 use arrayvec::ArrayVec;
 
 use crate::pieces::BoardIdx;
@@ -26,23 +24,9 @@ pub fn set<const VAL: bool>(index: usize, lib: &mut Liberties) {
     }
 }
 
-#[inline(always)]
-pub fn merge(lib: &mut Liberties, o: &Liberties) {
-    lib.iter_mut().zip(o).for_each(|(x, o)| x.bitor_assign(o))
-}
-
-#[inline(always)]
-fn any(lib: &Liberties) -> bool {
-    lib.iter().any(|&x| x != 0)
-}
-
-fn count_ones(lib: &Liberties) -> usize {
-    lib.iter().map(|x| x.count_ones() as usize).sum()
-}
-
-fn iter_ones(lib: &Liberties) -> impl Iterator<Item=usize> + '_ {
-    lib.iter().enumerate().flat_map(|(ix, chunk)| {
-        let mut chunk = *chunk;
+pub fn iter_ones(lib: Liberties) -> impl Iterator<Item=usize> {
+    lib.into_iter().enumerate().flat_map(|(ix, chunk)| {
+        let mut chunk = chunk;
         let mut ixs = ArrayVec::<usize, BITS>::new();
         let mut index = 0;
         while chunk != 0 {
@@ -55,34 +39,41 @@ fn iter_ones(lib: &Liberties) -> impl Iterator<Item=usize> + '_ {
     })
 }
 
-fn get(index: usize, lib: &Liberties) -> bool {
-    let chunk = index / BITS;
-    let bit_index = index % BITS;
-    (lib[chunk] & (1 << bit_index)) != 0
-}
-
+/// A chain of stones, the stones are linked on the goban. Only the number of liberties is
+/// stored to keep the chain small, the liberties are found on the goban with
+/// `Goban::get_chain_liberties_it`.
 #[derive(Clone, Debug, PartialEq, Eq, Copy)]
 pub struct Chain {
     pub color: Color,
     pub origin: u16,
     pub last: u16,
-    pub liberties: Liberties,
+    pub num_liberties: u16,
     pub used: bool,
     pub num_stones: u16,
 }
 
 impl Chain {
+    /// The content of the slots never used.
+    pub const UNUSED: Chain = Chain {
+        color: Color::Black,
+        origin: 0,
+        last: 0,
+        num_liberties: 0,
+        used: false,
+        num_stones: 0,
+    };
+
     #[inline]
     pub fn new(color: Color, stone: BoardIdx) -> Self {
-        Self::new_with_liberties(color, stone, Default::default())
+        Self::new_with_liberties(color, stone, 0)
     }
 
-    pub fn new_with_liberties(color: Color, stone: BoardIdx, liberties: Liberties) -> Self {
+    pub fn new_with_liberties(color: Color, stone: BoardIdx, num_liberties: u16) -> Self {
         Chain {
             color,
             origin: stone as u16,
             last: stone as u16,
-            liberties,
+            num_liberties,
             used: true,
             num_stones: 1,
         }
@@ -90,78 +81,33 @@ impl Chain {
 
     #[inline]
     pub fn is_dead(&self) -> bool {
-        // !self.liberties.any()
-        !any(&self.liberties)
+        self.num_liberties == 0
     }
 
     #[inline]
     pub fn number_of_liberties(&self) -> usize {
-        //self.liberties.count_ones()
-        count_ones(&self.liberties)
+        self.num_liberties as usize
     }
 
     /// A go string is atari if it only has one liberty
     #[inline]
     pub fn is_atari(&self) -> bool {
-        self.number_of_liberties() == 1
-    }
-
-    #[inline]
-    pub fn contains_liberty(&self, stone_idx: BoardIdx) -> bool {
-        //self.liberties[stone_idx]
-        get(stone_idx, &self.liberties)
-    }
-
-    #[inline]
-    pub fn remove_liberty(&mut self, stone_idx: BoardIdx) -> &mut Self {
-        debug_assert!(
-            get(stone_idx, &self.liberties),
-            "Tried to remove a liberty, who isn't present. stone idx: {stone_idx}"
-        );
-        set::<false>(stone_idx, &mut self.liberties);
-        self
-    }
-
-    #[inline(always)]
-    fn add_liberty_unchecked(&mut self, stone_idx: BoardIdx) -> &mut Self {
-        set::<true>(stone_idx, &mut self.liberties);
-        self
+        self.num_liberties == 1
     }
 
     #[inline]
-    pub fn add_liberty(&mut self, stone_idx: BoardIdx) -> &mut Self {
+    pub fn remove_liberty(&mut self) -> &mut Self {
         debug_assert!(
-            //self.liberties[stone_idx],
-            !get(stone_idx, &self.liberties),
-            "Tried to add a liberty already present, stone idx: {stone_idx}"
+            self.num_liberties > 0,
+            "Tried to remove a liberty of a dead chain"
         );
-        //self.liberties.set(stone_idx, true);
-        self.add_liberty_unchecked(stone_idx)
-    }
-
-    #[inline]
-    pub fn add_liberties(&mut self, stones_idx: impl Iterator<Item=BoardIdx>) -> &mut Self {
-        for idx in stones_idx {
-            self.add_liberty(idx);
-        }
+        self.num_liberties -= 1;
         self
     }
 
     #[inline]
-    pub fn union_liberties(&mut self, liberties_idx: Liberties) -> &mut Self {
-        //self.liberties |= liberties_idx;
-        merge(&mut self.liberties, &liberties_idx);
+    pub fn add_liberty(&mut self) -> &mut Self {
+        self.num_liberties += 1;
         self
     }
-
-    pub fn union_liberties_slice(&mut self, stones_idx: &[BoardIdx]) -> &mut Self {
-        for &idx in stones_idx {
-            self.add_liberty_unchecked(idx);
-        }
-        self
-    }
-
-    pub fn liberties(&self) -> Vec<usize> {
-        iter_ones(&self.liberties).collect()
-    }
 }
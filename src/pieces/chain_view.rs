@@ -0,0 +1,142 @@
+//! Module for reading the chains of a goban without exposing the internal representation.
+//! # Example
+//! ```
+//! use goban::pieces::goban::Goban;
+//! use goban::pieces::stones::Color;
+//!
+//! let goban: Goban = "
+//!     . X O .
+//!     . X O .
+//!     . . . .
+//! ".parse().unwrap();
+//! let black = goban.chain_at((0, 1)).unwrap();
+//! assert_eq!(black.color(), Color::Black);
+//! assert_eq!(black.stones().collect::<Vec<_>>(), vec![(0, 1), (1, 1)]);
+//! assert_eq!(black.number_of_liberties(), 3);
+//! assert_eq!(black.adjacent_enemies().count(), 1);
+//! assert_eq!(goban.chains().count(), 2);
+//! ```
+
+use std::fmt::{Debug, Formatter};
+
+use crate::pieces::chain::Chain;
+use crate::pieces::goban::{ChainIdx, Goban};
+use crate::pieces::stones::Color;
+use crate::pieces::util::coord::{is_coord_valid, one_to_2dim, two_to_1dim, Coord};
+
+/// Read only view of a chain of stones on a goban.
+#[derive(Clone, Copy)]
+pub struct ChainView<'a> {
+    goban: &'a Goban,
+    index: ChainIdx,
+}
+
+impl<'a> ChainView<'a> {
+    #[inline]
+    fn chain(&self) -> &'a Chain {
+        self.goban.get_chain(self.index)
+    }
+
+    /// The index of the chain in the goban, it identifies the chain until the next move.
+    #[inline]
+    pub fn index(&self) -> ChainIdx {
+        self.index
+    }
+
+    #[inline]
+    pub fn color(&self) -> Color {
+        self.chain().color
+    }
+
+    /// The first stone of the chain.
+    #[inline]
+    pub fn origin(&self) -> Coord {
+        one_to_2dim(self.goban.size(), self.chain().origin as usize)
+    }
+
+    #[inline]
+    pub fn number_of_stones(&self) -> usize {
+        self.chain().num_stones as usize
+    }
+
+    /// The coordinates of the stones, starting by the origin.
+    pub fn stones(&self) -> impl Iterator<Item = Coord> + 'a {
+        let size = self.goban.size();
+        self.goban
+            .get_chain_it(self.index)
+            .map(move |board_idx| one_to_2dim(size, board_idx))
+    }
+
+    /// The coordinates of the liberties in row major order.
+    pub fn liberties(&self) -> impl Iterator<Item = Coord> + 'a {
+        let size = self.goban.size();
+        self.goban
+            .get_chain_liberties_it(self.index)
+            .map(move |board_idx| one_to_2dim(size, board_idx))
+    }
+
+    #[inline]
+    pub fn number_of_liberties(&self) -> usize {
+        self.chain().number_of_liberties()
+    }
+
+    #[inline]
+    pub fn is_atari(&self) -> bool {
+        self.chain().is_atari()
+    }
+
+    /// The chains of the opposite color touching this chain, each one is returned once.
+    pub fn adjacent_enemies(&self) -> impl Iterator<Item = ChainView<'a>> + 'a {
+        let goban = self.goban;
+        let color = self.color();
+        let mut indexes: Vec<ChainIdx> = self
+            .stones()
+            .flat_map(|coord| goban.get_neighbors_chain_indexes(coord))
+            .filter(|&index| goban.get_chain(index).color != color)
+            .collect();
+        indexes.sort_unstable();
+        indexes.dedup();
+        indexes
+            .into_iter()
+            .map(move |index| ChainView { goban, index })
+    }
+}
+
+impl PartialEq for ChainView<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.goban, other.goban) && self.index == other.index
+    }
+}
+
+impl Eq for ChainView<'_> {}
+
+impl Debug for ChainView<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChainView")
+            .field("index", &self.index)
+            .field("color", &self.color())
+            .field("origin", &self.origin())
+            .field("stones", &self.number_of_stones())
+            .field("liberties", &self.number_of_liberties())
+            .finish()
+    }
+}
+
+impl Goban {
+    /// Iterates over the chains on the goban, the captured chains are skipped.
+    pub fn chains(&self) -> impl Iterator<Item = ChainView<'_>> + '_ {
+        (0..self.chains.len())
+            .filter(move |&index| self.chains[index].used)
+            .map(move |index| ChainView { goban: self, index })
+    }
+
+    /// Returns the chain of the stone on this point, None if the point is empty or outside
+    /// the goban.
+    pub fn chain_at(&self, coord: Coord) -> Option<ChainView<'_>> {
+        if !is_coord_valid(self.size(), coord) {
+            return None;
+        }
+        self.chain_idx_at(two_to_1dim(self.size(), coord))
+            .map(|index| ChainView { goban: self, index })
+    }
+}
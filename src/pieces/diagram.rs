@@ -0,0 +1,170 @@
+//! Module for parsing text diagrams of a goban.
+//!
+//! The accepted characters are `X`/`●` for Black, `O`/`○` for White, and `.`, `+`, `,` or the
+//! box drawing characters of `pretty_string` for empty intersections. A `*` marks an empty
+//! intersection as the ko point. The intersections can be separated by spaces, and the
+//! coordinate labels (letters of the columns, numbers of the lines) are ignored.
+//! A line like "Black to play" or "W to move" gives the side to move.
+
+use std::str::FromStr;
+
+use crate::pieces::goban::Goban;
+use crate::pieces::stones::{Color, MaybeColor, EMPTY};
+use crate::pieces::util::coord::{Coord, Size};
+use crate::pieces::Nat;
+
+/// A position read from a text diagram.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagram {
+    pub size: Size,
+    /// Intersections in a RowMajor Policy.
+    pub points: Vec<MaybeColor>,
+    /// The side to move if the diagram tells it.
+    pub turn: Option<Color>,
+    pub ko_point: Option<Coord>,
+}
+
+/// Returns the content of the intersection, None if the character isn't an intersection.
+fn parse_intersection(c: char) -> Option<(MaybeColor, bool)> {
+    match c {
+        'X' | 'x' | '●' | '#' => Some((Some(Color::Black), false)),
+        'O' | 'o' | '○' => Some((Some(Color::White), false)),
+        '*' => Some((EMPTY, true)),
+        '.' | '+' | ',' | '┏' | '┓' | '┗' | '┛' | '┯' | '┷' | '┠' | '┨' | '┼' => {
+            Some((EMPTY, false))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the side to move if the line is like "Black to play".
+fn parse_turn(line: &str) -> Option<Color> {
+    let line = line.to_lowercase();
+    let (color, rest) = line.split_once(char::is_whitespace)?;
+    if !(rest.trim() == "to play" || rest.trim() == "to move") {
+        return None;
+    }
+    match color {
+        "b" | "black" => Some(Color::Black),
+        "w" | "white" => Some(Color::White),
+        _ => None,
+    }
+}
+
+impl FromStr for Diagram {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut points = vec![];
+        let mut turn = None;
+        let mut ko_point = None;
+        let mut height: Nat = 0;
+        let mut width = None;
+        for line in s.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if let Some(color) = parse_turn(line) {
+                turn = Some(color);
+                continue;
+            }
+            let row: String = line
+                .chars()
+                .filter(|c| !c.is_whitespace() && *c != '|')
+                .collect();
+            // Line labels on both sides.
+            let row = row.trim_matches(|c: char| c.is_ascii_digit());
+            if row.is_empty() || row.contains('-') {
+                // Borders
+                continue;
+            }
+            let Some(intersections) = row
+                .chars()
+                .map(parse_intersection)
+                .collect::<Option<Vec<_>>>()
+            else {
+                // Column labels
+                if row.chars().all(|c| c.is_ascii_alphabetic()) {
+                    continue;
+                }
+                return Err(format!("Invalid line in the diagram: {line}"));
+            };
+            if *width.get_or_insert(intersections.len()) != intersections.len() {
+                return Err(format!(
+                    "The line {line} doesn't have the size of the others"
+                ));
+            }
+            for (column, (color, is_ko)) in intersections.into_iter().enumerate() {
+                if is_ko {
+                    if ko_point.is_some() {
+                        return Err("The diagram has many ko points".to_string());
+                    }
+                    ko_point = Some((height, column as Nat));
+                }
+                points.push(color);
+            }
+            height += 1;
+        }
+        let width = width.ok_or("The diagram is empty")? as Nat;
+        if height > 19 || width > 19 {
+            return Err(format!("The diagram is too big: {height}x{width}"));
+        }
+        Ok(Diagram {
+            size: (height, width),
+            points,
+            turn,
+            ko_point,
+        })
+    }
+}
+
+impl Diagram {
+    /// Creates the goban of the diagram.
+    /// # Errors
+    /// If a chain of the diagram doesn't have liberties.
+    pub fn to_goban(&self) -> Result<Goban, String> {
+        let mut goban = Goban::new(self.size);
+        for (index, color) in self.points.iter().enumerate() {
+            if let &Some(color) = color {
+                let coord = (
+                    (index / self.size.1 as usize) as Nat,
+                    (index % self.size.1 as usize) as Nat,
+                );
+                goban.push(coord, color);
+            }
+        }
+        if let Some(chain) = goban
+            .chains
+            .iter()
+            .find(|chain| chain.used && chain.is_dead())
+        {
+            return Err(format!(
+                "A {} chain of the diagram doesn't have liberties",
+                chain.color
+            ));
+        }
+        Ok(goban)
+    }
+}
+
+/// Parses a goban from a text diagram, like the output of `pretty_string`.
+/// # Example
+/// ```
+/// use goban::pieces::goban::Goban;
+/// use goban::pieces::stones::Color;
+///
+/// let goban: Goban = "
+///    A B C D
+///  4 . . . .
+///  3 . X O .
+///  2 . X O .
+///  1 . . . .
+/// ".parse().unwrap();
+/// assert_eq!(goban.size(), (4, 4));
+/// assert_eq!(goban.get_color((1, 1)), Some(Color::Black));
+/// assert_eq!(goban.get_color((2, 2)), Some(Color::White));
+/// ```
+impl FromStr for Goban {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<Diagram>()?.to_goban()
+    }
+}
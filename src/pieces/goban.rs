@@ -4,11 +4,12 @@ use std::fmt::Display;
 use std::fmt::Error;
 use std::fmt::Formatter;
 use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut};
 
 use arrayvec::ArrayVec;
 
 use crate::one2dim;
-use crate::pieces::chain::{Chain, Liberties, merge, set};
+use crate::pieces::chain::{Chain, iter_ones, Liberties, set};
 use crate::pieces::Nat;
 use crate::pieces::stones::*;
 use crate::pieces::util::CircularRenIter;
@@ -16,13 +17,20 @@ use crate::pieces::util::coord::{
     Coord, is_coord_valid, neighbor_coords, one_to_2dim, Size, two_to_1dim,
 };
 use crate::pieces::zobrist::*;
+use crate::rules::PlayError;
 
 pub type ChainIdx = usize;
 pub type BoardIdx = usize;
 
 const BOARD_MAX_SIZE: (Nat, Nat) = (19, 19);
 const BOARD_MAX_LENGTH: usize = BOARD_MAX_SIZE.0 as usize * BOARD_MAX_SIZE.1 as usize;
-const MAX_CHAINS: usize = 4 * BOARD_MAX_LENGTH / 5;
+/// The stones pushed without the rules can fill the goban with chains without liberties, so
+/// every point can be a chain.
+const MAX_CHAINS: usize = BOARD_MAX_LENGTH;
+/// A chain has at least one stone, so there are never more chains than points.
+const FREE_SLOTS_LEN: usize = BOARD_MAX_LENGTH / u64::BITS as usize + 1;
+/// The value on the board of the empty points.
+const NO_CHAIN: u16 = u16::MAX;
 
 macro_rules! iter_stones {
     ($goban: expr, $ren_idx: expr) => {
@@ -33,31 +41,140 @@ macro_rules! iter_stones {
     };
 }
 
+/// The chains of the goban in a fixed size array, unlike an `ArrayVec` it's `Copy`.
+/// Derefs to the slice of the slots allocated.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Chains {
+    slots: [Chain; MAX_CHAINS],
+    len: u16,
+}
+
+impl Chains {
+    const fn new() -> Self {
+        Chains {
+            slots: [Chain::UNUSED; MAX_CHAINS],
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, chain: Chain) {
+        self.slots[self.len as usize] = chain;
+        self.len += 1;
+    }
+
+    #[inline]
+    fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len as u16);
+    }
+}
+
+impl Deref for Chains {
+    type Target = [Chain];
+
+    #[inline]
+    fn deref(&self) -> &[Chain] {
+        &self.slots[..self.len as usize]
+    }
+}
+
+impl DerefMut for Chains {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [Chain] {
+        &mut self.slots[..self.len as usize]
+    }
+}
+
 /// Represents a goban. the stones are stored in ROW MAJOR (row, column)
-#[derive(Debug, Clone)]
+/// The goban is stored in fixed size arrays for the biggest goban, without allocation, so the
+/// goban is `Copy` and a clone is a copy of the memory.
+#[derive(Debug, Clone, Copy)]
 pub struct Goban {
-    pub(super) chains: Vec<Chain>,
-    //free_slots: BitArr!(for MAX_CHAINS),
-    board: Vec<Option<u16>>,
-    next_stone: Vec<u16>,
+    pub(super) chains: Chains,
+    /// Bitset of the slots of `chains` freed by the captures and the merges.
+    free_slots: [u64; FREE_SLOTS_LEN],
+    /// The index of the chain of the stone on every point, `NO_CHAIN` if the point is empty.
+    board: [u16; BOARD_MAX_LENGTH],
+    next_stone: [u16; BOARD_MAX_LENGTH],
     size: Size,
     zobrist_hash: u64,
 }
 
+/// What a move played by `Goban::apply` changed, to put the goban back with `Goban::revert`.
+/// It lives on the stack, so a search can play and take back moves without allocation.
+#[derive(Debug, Clone)]
+#[must_use = "The move can only be taken back with the undo record"]
+pub struct Undo {
+    point: BoardIdx,
+    /// The hash before the move.
+    hash: u64,
+    free_slots: [u64; FREE_SLOTS_LEN],
+    chains_len: usize,
+    /// The chains of the same color joined by the move, and the slot the new chain could take.
+    chains: ArrayVec<(ChainIdx, Chain), 5>,
+    /// True if chains were merged, their stones changed of index on the board.
+    merged: bool,
+    /// The links of the chains changed by the merges.
+    next_stones: ArrayVec<(BoardIdx, u16), 5>,
+    /// The chains of the other color who lost a liberty.
+    opponents: ArrayVec<ChainIdx, 4>,
+    captured: ArrayVec<ChainIdx, 4>,
+    captured_stones: u32,
+    ko_point: Option<Coord>,
+}
+
+impl Undo {
+    /// The number of stones captured by the move.
+    #[inline]
+    pub fn captured_stones(&self) -> u32 {
+        self.captured_stones
+    }
+
+    /// The point of the ko created by the move, if only one stone was captured.
+    #[inline]
+    pub fn ko_point(&self) -> Option<Coord> {
+        self.ko_point
+    }
+
+    /// The hash of the goban before the move.
+    #[inline]
+    pub fn previous_hash(&self) -> u64 {
+        self.hash
+    }
+}
+
 impl Goban {
     /// Creates a Goban
     /// # Arguments
     ///
     /// * `(height, width)` a tuple with the height and the width of the desired goban.
+    ///
+    /// # Panics
+    /// if a side is bigger than 19, see `try_new` for a checked version.
     pub fn new((height, width): Size) -> Self {
-        assert!(height <= 19 && width <= 19,);
+        assert!(
+            height <= 19 && width <= 19,
+            "The goban {height}x{width} is too big"
+        );
         Goban {
             size: (height, width),
             zobrist_hash: 0,
-            board: vec![None; BOARD_MAX_LENGTH],
-            next_stone: vec![0; BOARD_MAX_LENGTH],
-            chains: Vec::with_capacity(MAX_CHAINS),
-            //free_slots: Default::default(),
+            board: [NO_CHAIN; BOARD_MAX_LENGTH],
+            next_stone: [0; BOARD_MAX_LENGTH],
+            chains: Chains::new(),
+            free_slots: [0; FREE_SLOTS_LEN],
+        }
+    }
+
+    /// Creates a Goban, the sides must be between 1 and 19.
+    ///
+    /// # Errors
+    /// InvalidSize if a side is 0 or bigger than 19.
+    pub fn try_new((height, width): Size) -> Result<Self, PlayError> {
+        if (1..=19).contains(&height) && (1..=19).contains(&width) {
+            Ok(Goban::new((height, width)))
+        } else {
+            Err(PlayError::InvalidSize)
         }
     }
 
@@ -76,22 +193,28 @@ impl Goban {
         game
     }
 
+    #[inline]
     pub fn size(&self) -> Size {
         self.size
     }
 
+    #[inline]
     pub fn zobrist_hash(&self) -> u64 {
         self.zobrist_hash
     }
 
+    /// Number of slots allocated for the chains, used or freed. The freed slots are reused so
+    /// it's never more than the biggest number of chains the goban had at once.
+    pub fn chain_slots(&self) -> usize {
+        self.chains.len()
+    }
+
     /// Returns the underlying goban in a vector with a RowMajor Policy, calculated on the fly.
     pub fn to_vec(&self) -> Vec<MaybeColor> {
-        self.board
-            .iter()
+        (0..BOARD_MAX_LENGTH)
             .map(|point| {
-                point.map_or(EMPTY, |go_str_ptr| {
-                    self.chains[go_str_ptr as usize].color.into()
-                })
+                self.chain_idx_at(point)
+                    .map_or(EMPTY, |go_str_ptr| self.chains[go_str_ptr].color.into())
             })
             .collect()
     }
@@ -102,7 +225,10 @@ impl Goban {
         for line in self.board.chunks_exact(self.size.1 as usize) {
             let v = line
                 .iter()
-                .map(|o| o.map_or(EMPTY, |idx| self.chains[idx as usize].color.into()))
+                .map(|&idx| match idx {
+                    NO_CHAIN => EMPTY,
+                    idx => self.chains[idx as usize].color.into(),
+                })
                 .collect();
             mat.push(v);
         }
@@ -119,9 +245,13 @@ impl Goban {
             })
     }
 
-    #[inline]
-    pub(crate) fn board(&self) -> &[Option<u16>] {
-        &self.board
+    /// The index of the chain of the stone at the board index, None if the point is empty.
+    #[inline(always)]
+    pub(crate) fn chain_idx_at(&self, board_idx: BoardIdx) -> Option<ChainIdx> {
+        match self.board[board_idx] {
+            NO_CHAIN => None,
+            chain_idx => Some(chain_idx as ChainIdx),
+        }
     }
 
     /// pushes the stone
@@ -134,6 +264,18 @@ impl Goban {
         &mut self,
         point: Coord,
         color: Color,
+    ) -> (ArrayVec<usize, 4>, ChainIdx) {
+        self.push_recorded(point, color, |_, _, _| {})
+    }
+
+    /// Same as `push_wth_feedback`, `before` is called with the chains of the same color and
+    /// the chains of the other color around the point, before the goban changes.
+    #[inline(always)]
+    fn push_recorded(
+        &mut self,
+        point: Coord,
+        color: Color,
+        before: impl FnOnce(&Self, &[ChainIdx], &[ChainIdx]),
     ) -> (ArrayVec<usize, 4>, ChainIdx) {
         let pushed_stone_idx = two_to_1dim(self.size, point);
 
@@ -142,9 +284,8 @@ impl Goban {
         let mut liberties = ArrayVec::<BoardIdx, 4>::new();
 
         for neighbor_idx in self.neighbors_idx(pushed_stone_idx) {
-            match self.board[neighbor_idx] {
+            match self.chain_idx_at(neighbor_idx) {
                 Some(adj_ren_index) => {
-                    let adj_ren_index = adj_ren_index as usize;
                     if self.chains[adj_ren_index].color == color {
                         if !adjacent_same_color_str_set.contains(&adj_ren_index) {
                             adjacent_same_color_str_set.push(adj_ren_index);
@@ -158,12 +299,17 @@ impl Goban {
                 }
             }
         }
+        before(
+            self,
+            &adjacent_same_color_str_set,
+            &adjacent_opposite_color_str_set,
+        );
         let mut dead_ren = ArrayVec::<BoardIdx, 4>::new();
         // for every string of opposite color remove a liberty and update the string.
         for ren_idx in adjacent_opposite_color_str_set {
             let ren = &mut self.chains[ren_idx];
             if ren.used {
-                ren.remove_liberty(pushed_stone_idx);
+                ren.remove_liberty();
                 if ren.is_dead() {
                     dead_ren.push(ren_idx);
                 }
@@ -172,19 +318,32 @@ impl Goban {
 
         let number_of_neighbors_strings = adjacent_same_color_str_set.len();
         let updated_ren_index = match number_of_neighbors_strings {
-            0 => self.create_chain(pushed_stone_idx, color, &liberties),
+            0 => self.create_chain(pushed_stone_idx, color, liberties.len() as u16),
             1 => {
                 let only_ren_idx = adjacent_same_color_str_set[0];
-
-                self.chains[only_ren_idx]
-                    .remove_liberty(pushed_stone_idx)
-                    .union_liberties_slice(&liberties);
+                // The empty neighbors already next to the chain are already liberties.
+                let new_liberties = liberties
+                    .iter()
+                    .filter(|&&liberty| {
+                        !self
+                            .neighbors_idx(liberty)
+                            .any(|n| self.board[n] == only_ren_idx as u16)
+                    })
+                    .count() as u16;
+                let chain = &mut self.chains[only_ren_idx];
+                chain.num_liberties = chain.num_liberties - 1 + new_liberties;
                 self.add_stone_to_chain(only_ren_idx, pushed_stone_idx);
-                self.board[pushed_stone_idx] = Some(only_ren_idx as u16);
+                self.board[pushed_stone_idx] = only_ren_idx as u16;
                 only_ren_idx
             }
             _ => {
-                let mut to_merge = self.create_chain(pushed_stone_idx, color, &liberties);
+                let num_liberties = self.merged_liberties(
+                    pushed_stone_idx,
+                    &adjacent_same_color_str_set,
+                    &liberties,
+                );
+                let mut to_merge =
+                    self.create_chain(pushed_stone_idx, color, liberties.len() as u16);
                 for adj_ren in adjacent_same_color_str_set {
                     if self.chains[adj_ren].number_of_liberties()
                         < self.chains[to_merge].number_of_liberties()
@@ -195,7 +354,7 @@ impl Goban {
                         to_merge = adj_ren;
                     }
                 }
-                self.chains[to_merge].remove_liberty(pushed_stone_idx);
+                self.chains[to_merge].num_liberties = num_liberties;
                 to_merge
             }
         };
@@ -246,6 +405,141 @@ impl Goban {
         (stones_removed, ko_point)
     }
 
+    /// Plays a stone in place and removes the chains it captures, the move is taken back by
+    /// `revert` with the returned record. Like `push` the point isn't checked and a stone
+    /// without liberties stays on the goban.
+    /// # Example
+    /// ```
+    /// use goban::pieces::goban::Goban;
+    /// use goban::pieces::stones::Color;
+    ///
+    /// let mut goban = Goban::new((9, 9));
+    /// goban.push((0, 0), Color::White).push((0, 1), Color::Black);
+    /// let before = goban.clone();
+    /// let undo = goban.apply((1, 0), Color::Black);
+    /// assert_eq!(goban.get_color((0, 0)), None);
+    /// assert_eq!(undo.captured_stones(), 1);
+    /// assert_eq!(undo.ko_point(), Some((0, 0)));
+    /// goban.revert(undo);
+    /// assert_eq!(goban, before);
+    /// assert_eq!(goban.get_color((0, 0)), Some(Color::White));
+    /// ```
+    #[inline]
+    pub fn apply(&mut self, point: Coord, color: Color) -> Undo {
+        let point_idx = two_to_1dim(self.size, point);
+        let mut undo = Undo {
+            point: point_idx,
+            hash: self.zobrist_hash,
+            free_slots: self.free_slots,
+            chains_len: self.chains.len(),
+            chains: ArrayVec::new(),
+            merged: false,
+            next_stones: ArrayVec::new(),
+            opponents: ArrayVec::new(),
+            captured: ArrayVec::new(),
+            captured_stones: 0,
+            ko_point: None,
+        };
+        let (dead_chains, _) = self.push_recorded(point, color, |goban, same, opposite| {
+            undo.next_stones
+                .push((point_idx, goban.next_stone[point_idx]));
+            for &chain_idx in same {
+                let chain = goban.chains[chain_idx];
+                undo.chains.push((chain_idx, chain));
+                let last = chain.last as usize;
+                undo.next_stones.push((last, goban.next_stone[last]));
+            }
+            undo.merged = same.len() > 1;
+            if same.len() != 1 {
+                // A new chain is created.
+                if let Some(slot) = goban.first_free_slot() {
+                    undo.chains.push((slot, goban.chains[slot]));
+                }
+            }
+            undo.opponents.try_extend_from_slice(opposite).unwrap();
+        });
+        for &dead_chain in &dead_chains {
+            let Chain {
+                num_stones, origin, ..
+            } = self.chains[dead_chain];
+            if num_stones == 1 && dead_chains.len() == 1 {
+                undo.ko_point = Some(one_to_2dim(self.size, origin as usize));
+            }
+            undo.captured_stones += num_stones as u32;
+            self.remove_chain(dead_chain);
+        }
+        undo.captured = dead_chains;
+        undo
+    }
+
+    /// Returns the hash of the goban if the stone was played, with the captures, without
+    /// playing it.
+    pub fn hash_after(&self, point: Coord, color: Color) -> u64 {
+        let point_idx = two_to_1dim(self.size, point);
+        let mut hash = self.zobrist_hash ^ index_zobrist(point_idx, color);
+        let mut captured = ArrayVec::<ChainIdx, 4>::new();
+        for chain_idx in self.get_neighbors_chains_ids_by_board_idx(point_idx) {
+            let chain = &self.chains[chain_idx];
+            if chain.color != color && chain.is_atari() && !captured.contains(&chain_idx) {
+                captured.push(chain_idx);
+                for stone in self.get_chain_it(chain_idx) {
+                    hash ^= index_zobrist(stone, chain.color);
+                }
+            }
+        }
+        hash
+    }
+
+    /// Takes back the move of the undo record, the moves must be taken back in the reverse
+    /// order they were applied.
+    #[inline]
+    pub fn revert(&mut self, undo: Undo) {
+        // The captured chains are untouched in their slots, only the board forgot them.
+        let mut neighbors = ArrayVec::<ChainIdx, 4>::new();
+        for &chain_idx in undo.captured.iter().rev() {
+            self.chains[chain_idx].used = true;
+            let color = self.chains[chain_idx].color;
+            for point_idx in iter_stones!(self, chain_idx) {
+                for neighbor in self.get_neighbors_chains_ids_by_board_idx(point_idx) {
+                    if neighbor != chain_idx && !neighbors.contains(&neighbor) {
+                        neighbors.push(neighbor);
+                    }
+                }
+                for &n in &neighbors {
+                    self.chains[n].remove_liberty();
+                }
+                neighbors.clear();
+                self.board[point_idx] = chain_idx as u16;
+                self.zobrist_hash ^= index_zobrist(point_idx, color);
+            }
+        }
+
+        self.board[undo.point] = NO_CHAIN;
+        for &(chain_idx, chain) in undo.chains.iter().rev() {
+            if chain_idx < self.chains.len() {
+                self.chains[chain_idx] = chain;
+            }
+        }
+        self.chains.truncate(undo.chains_len);
+        for &(board_idx, next) in undo.next_stones.iter().rev() {
+            self.next_stone[board_idx] = next;
+        }
+        if undo.merged {
+            for &(chain_idx, chain) in &undo.chains {
+                if chain.used {
+                    self.update_chain_indexes_in_board(chain_idx);
+                }
+            }
+        }
+        for &chain_idx in &undo.opponents {
+            self.chains[chain_idx].add_liberty();
+        }
+        self.free_slots = undo.free_slots;
+        self.zobrist_hash = undo.hash;
+        #[cfg(debug_assertions)]
+        self.check_integrity_all();
+    }
+
     /// Put a stones in the goban.
     /// default (line, column)
     /// the (0,0) point is in the top left.
@@ -267,6 +561,21 @@ impl Goban {
         self
     }
 
+    /// Put a stone in the goban, but checks the point first.
+    /// It doesn't check the liberties, a stone without liberties stays on the goban.
+    ///
+    /// # Errors
+    /// OutOfBounds if the point is outside the goban, PointNotEmpty if there is already a stone.
+    pub fn try_push(&mut self, point: Coord, color: Color) -> Result<&mut Self, PlayError> {
+        if !is_coord_valid(self.size, point) {
+            Err(PlayError::OutOfBounds)
+        } else if self.get_color(point).is_some() {
+            Err(PlayError::PointNotEmpty)
+        } else {
+            Ok(self.push(point, color))
+        }
+    }
+
     /// Helper function to put a stone.
     #[inline]
     pub fn push_stone(&mut self, stone: Stone) -> &mut Goban {
@@ -281,10 +590,18 @@ impl Goban {
         })
     }
 
+    #[inline]
+    pub fn get_chain(&self, chain_idx: ChainIdx) -> &Chain {
+        &self.chains[chain_idx]
+    }
+
+    #[inline]
     pub fn get_chain_by_board_idx(&self, board_idx: BoardIdx) -> Option<&Chain> {
-        self.board[board_idx].map(|chain| &self.chains[chain as usize])
+        self.chain_idx_at(board_idx)
+            .map(|chain| &self.chains[chain])
     }
 
+    #[inline]
     pub fn get_chain_by_point(&self, point: Coord) -> Option<&Chain> {
         self.get_chain_by_board_idx(two_to_1dim(self.size, point))
     }
@@ -315,7 +632,7 @@ impl Goban {
     pub fn get_neighbors_chain_indexes(&self, coord: Coord) -> impl Iterator<Item=ChainIdx> + '_ {
         self.neighbors_coords(coord)
             .map(move |point| two_to_1dim(self.size, point))
-            .filter_map(move |point| self.board[point].map(|idx| idx as usize))
+            .filter_map(move |point| self.chain_idx_at(point))
     }
 
     /// Get all the chains adjacent to the point. The result iterator can contains duplicates.
@@ -331,7 +648,7 @@ impl Goban {
         index: BoardIdx,
     ) -> impl Iterator<Item=ChainIdx> + '_ {
         self.neighbors_idx(index)
-            .filter_map(move |idx| self.board[idx].map(|idx| idx as usize))
+            .filter_map(move |idx| self.chain_idx_at(idx))
     }
 
     /// Function for getting the stone in the goban.
@@ -339,14 +656,16 @@ impl Goban {
     pub fn get_point(&self, coord: Coord) -> Point {
         Point {
             coord,
-            color: self.board[two_to_1dim(self.size, coord)]
-                .map(|chain_idx| self.chains[chain_idx as usize].color),
+            color: self
+                .chain_idx_at(two_to_1dim(self.size, coord))
+                .map(|chain_idx| self.chains[chain_idx].color),
         }
     }
 
+    #[inline]
     pub fn get_color(&self, coord: Coord) -> MaybeColor {
-        self.board[two_to_1dim(self.size, coord)]
-            .map(|chain_id| self.chains[chain_id as usize].color)
+        self.chain_idx_at(two_to_1dim(self.size, coord))
+            .map(|chain_id| self.chains[chain_id].color)
     }
 
     pub fn get_stone_color(&self, coord: Coord) -> Color {
@@ -356,10 +675,10 @@ impl Goban {
     /// Get all the stones except "EMPTY stones"
     #[inline]
     pub fn get_stones(&self) -> impl Iterator<Item=Stone> + '_ {
-        self.board.iter().enumerate().filter_map(move |(index, o)| {
-            o.map(move |chain_idx| Stone {
+        (0..BOARD_MAX_LENGTH).filter_map(move |index| {
+            self.chain_idx_at(index).map(move |chain_idx| Stone {
                 coord: one_to_2dim(self.size, index),
-                color: self.chains[chain_idx as usize].color,
+                color: self.chains[chain_idx].color,
             })
         })
     }
@@ -372,19 +691,17 @@ impl Goban {
     }
 
     pub fn get_empty_idx(&self) -> impl Iterator<Item=BoardIdx> + '_ {
-        self.board
-            .iter()
-            .enumerate()
-            .filter_map(|(idx, chain)| chain.map(|_| idx))
+        (0..BOARD_MAX_LENGTH).filter(|&idx| self.chain_idx_at(idx).is_some())
     }
 
+    #[inline]
     pub fn get_empty_coords(&self) -> impl Iterator<Item=Coord> + '_ {
         let board_length = self.size.0 as usize * self.size.1 as usize;
         self.board[..board_length]
             .iter()
             .enumerate()
             .filter_map(|x| {
-                if x.1.is_none() {
+                if *x.1 == NO_CHAIN {
                     Some(one2dim!(self.size, x.0))
                 } else {
                     None
@@ -396,11 +713,12 @@ impl Goban {
     #[inline]
     pub fn get_coords_by_color(&self, color: MaybeColor) -> impl Iterator<Item=Coord> + '_ {
         let mut res = ArrayVec::<Coord, BOARD_MAX_LENGTH>::new();
-        for board_idx in 0..(self.size.0 * self.size.1) as usize {
+        for board_idx in 0..self.size.0 as usize * self.size.1 as usize {
             match color {
                 EMPTY => res.push(one_to_2dim(self.size, board_idx)),
-                Some(c) => self.board[board_idx]
-                    .filter(|&chain_idx| self.chains[chain_idx as usize].color == c)
+                Some(c) => self
+                    .chain_idx_at(board_idx)
+                    .filter(|&chain_idx| self.chains[chain_idx].color == c)
                     .map(|_| res.push(one_to_2dim(self.size, board_idx)))
                     .unwrap_or(()),
             }
@@ -420,7 +738,7 @@ impl Goban {
         self.get_liberties(coord).next().is_some()
     }
 
-    /// Get a string for printing the goban in normal shape (0,0) left bottom
+    /// Get a string for printing the goban, the (0,0) point is in the top left corner.
     pub fn pretty_string(&self) -> String {
         let mut buff = String::with_capacity(361);
         for i in 0..self.size.0 as Nat {
@@ -463,22 +781,17 @@ impl Goban {
 
         for point_idx in iter_stones!(self, ren_to_remove_idx as u16) {
             for neighbor_str_idx in self.get_neighbors_chains_ids_by_board_idx(point_idx) {
-                if ren_to_remove_idx != neighbor_str_idx {
-                    #[cfg(debug_assertions)]
-                    if !neighbors.contains(&neighbor_str_idx) {
-                        neighbors.push(neighbor_str_idx)
-                    }
-                    #[cfg(not(debug_assertions))]
+                if ren_to_remove_idx != neighbor_str_idx && !neighbors.contains(&neighbor_str_idx) {
                     neighbors.push(neighbor_str_idx)
                 }
             }
 
             for &n in &neighbors {
-                self.chains[n].add_liberty(point_idx);
+                self.chains[n].add_liberty();
             }
             neighbors.clear();
             self.zobrist_hash ^= index_zobrist(point_idx, color_of_the_string);
-            self.board[point_idx] = None;
+            self.board[point_idx] = NO_CHAIN;
         }
         self.put_chain_in_bin(ren_to_remove_idx);
     }
@@ -491,14 +804,14 @@ impl Goban {
         );
         for point in iter_stones!(self, ren_idx) {
             unsafe {
-                *self.board.get_unchecked_mut(point) = Some(ren_idx as u16);
+                *self.board.get_unchecked_mut(point) = ren_idx as u16;
             }
         }
     }
 
     /// Get the neighbors points of a point.
     #[inline]
-    fn neighbors_coords(&self, coord: Coord) -> impl Iterator<Item=Coord> {
+    pub(super) fn neighbors_coords(&self, coord: Coord) -> impl Iterator<Item=Coord> {
         let size = self.size;
         neighbor_coords(coord)
             .into_iter()
@@ -517,26 +830,108 @@ impl Goban {
         CircularRenIter::new(self.chains[chain_idx].origin as usize, &self.next_stone)
     }
 
+    /// Returns the coordinates of the stones of the chain.
+    pub fn get_chain_coords(&self, chain_idx: ChainIdx) -> Vec<Coord> {
+        self.get_chain_it(chain_idx)
+            .map(|board_idx| one_to_2dim(self.size, board_idx))
+            .collect()
+    }
+
+    /// Iterates over the board indexes of the liberties of the chain, in row major order.
+    pub fn get_chain_liberties_it(&self, chain_idx: ChainIdx) -> impl Iterator<Item=BoardIdx> {
+        iter_ones(self.chain_liberties(chain_idx))
+    }
+
+    fn chain_liberties(&self, chain_idx: ChainIdx) -> Liberties {
+        let mut liberties = Liberties::default();
+        for stone in self.get_chain_it(chain_idx) {
+            for neighbor in self.neighbors_idx(stone) {
+                if self.board[neighbor] == NO_CHAIN {
+                    set::<true>(neighbor, &mut liberties);
+                }
+            }
+        }
+        liberties
+    }
+
+    /// Number of liberties of the chains joined by the stone pushed at `stone_idx`, before
+    /// they are merged. The biggest chain keeps its liberties, so only the stones of the other
+    /// chains are visited: their liberties next to the biggest chain are already counted.
+    fn merged_liberties(
+        &self,
+        stone_idx: BoardIdx,
+        chains: &[ChainIdx],
+        stone_liberties: &[BoardIdx],
+    ) -> u16 {
+        let biggest = *chains
+            .iter()
+            .max_by_key(|&&chain_idx| self.chains[chain_idx].num_stones)
+            .expect("At least two chains are merged");
+        let is_new = |liberty: BoardIdx| {
+            liberty != stone_idx
+                && !self
+                    .neighbors_idx(liberty)
+                    .any(|n| self.board[n] == biggest as u16)
+        };
+        let mut new_liberties = Liberties::default();
+        for &liberty in stone_liberties {
+            if is_new(liberty) {
+                set::<true>(liberty, &mut new_liberties);
+            }
+        }
+        for &chain_idx in chains.iter().filter(|&&chain_idx| chain_idx != biggest) {
+            for stone in self.get_chain_it(chain_idx) {
+                for neighbor in self.neighbors_idx(stone) {
+                    if self.board[neighbor] == NO_CHAIN && is_new(neighbor) {
+                        set::<true>(neighbor, &mut new_liberties);
+                    }
+                }
+            }
+        }
+        // The stone was a liberty of the biggest chain.
+        self.chains[biggest].num_liberties - 1
+            + new_liberties
+                .iter()
+                .map(|bucket| bucket.count_ones() as u16)
+                .sum::<u16>()
+    }
+
+    #[cfg(debug_assertions)]
+    fn count_liberties(&self, chain_idx: ChainIdx) -> u16 {
+        self.chain_liberties(chain_idx)
+            .iter()
+            .map(|bucket| bucket.count_ones() as u16)
+            .sum()
+    }
+
     #[inline]
     pub fn get_chain_it_by_board_idx(
         &self,
         board_idx: BoardIdx,
     ) -> impl Iterator<Item=BoardIdx> + '_ {
-        self.board[board_idx]
-            .map(|chain_idx| self.get_chain_it(chain_idx as ChainIdx))
+        self.chain_idx_at(board_idx)
+            .map(|chain_idx| self.get_chain_it(chain_idx))
             .unwrap_or_else(|| panic!("The board index: {board_idx} was out of bounds"))
     }
 
     #[inline]
-    fn create_chain(&mut self, origin: BoardIdx, color: Color, liberties: &[BoardIdx]) -> ChainIdx {
-        let mut lib_bitvec: Liberties = Default::default();
-        for &board_idx in liberties {
-            set::<true>(board_idx, &mut lib_bitvec);
-        }
-        let chain_to_place = Chain::new_with_liberties(color, origin, lib_bitvec);
+    fn create_chain(&mut self, origin: BoardIdx, color: Color, liberties: u16) -> ChainIdx {
+        let chain_to_place = Chain::new_with_liberties(color, origin, liberties);
         self.next_stone[origin] = origin as u16;
-        self.chains.push(chain_to_place);
-        let chain_idx = self.chains.len() - 1;
+        // Reuses the first free slot, the vector grows only when all the slots are used.
+        let chain_idx = match self.free_slots.iter().position(|&bucket| bucket != 0) {
+            Some(bucket) => {
+                let bit = self.free_slots[bucket].trailing_zeros() as usize;
+                self.free_slots[bucket] &= !(1 << bit);
+                let chain_idx = bucket * u64::BITS as usize + bit;
+                self.chains[chain_idx] = chain_to_place;
+                chain_idx
+            }
+            None => {
+                self.chains.push(chain_to_place);
+                self.chains.len() - 1
+            }
+        };
         self.update_chain_indexes_in_board(chain_idx);
         chain_idx
     }
@@ -578,8 +973,6 @@ impl Goban {
                 &mut contains_chain2[chain2_idx],
             )
         };
-        merge(&mut chain1.liberties, &chain2.liberties);
-
         let chain1_last = chain1.last;
         let chain2_last = chain2.last;
 
@@ -599,10 +992,20 @@ impl Goban {
         self.put_chain_in_bin(chain2_idx);
     }
 
+    #[inline]
+    fn first_free_slot(&self) -> Option<ChainIdx> {
+        self.free_slots
+            .iter()
+            .position(|&bucket| bucket != 0)
+            .map(|bucket| {
+                bucket * u64::BITS as usize + self.free_slots[bucket].trailing_zeros() as usize
+            })
+    }
+
     #[inline]
     fn put_chain_in_bin(&mut self, ren_idx: ChainIdx) {
         self.chains[ren_idx].used = false;
-        //self.free_slots.set(ren_idx, true);
+        self.free_slots[ren_idx / u64::BITS as usize] |= 1 << (ren_idx % u64::BITS as usize);
     }
 
     #[allow(dead_code)]
@@ -621,6 +1024,11 @@ impl Goban {
         if iter_stones!(self, ren_idx).count() as u16 != self.chains[ren_idx].num_stones {
             panic!("The number of stones don't match")
         }
+        assert_eq!(
+            self.count_liberties(ren_idx),
+            self.chains[ren_idx].num_liberties,
+            "The number of liberties doesn't match"
+        );
     }
 
     #[allow(dead_code)]
@@ -0,0 +1,222 @@
+//! Module for grouping the chains of a goban in groups (also called dragons), the chains of
+//! one color that are connected even if their stones don't touch.
+//!
+//! Two chains of the same color are in the same group if they are linked by:
+//! - a diagonal connection, unless the two points between them are opponent stones.
+//! - a one point jump on an empty point.
+//! - at least two shared liberties, or one shared eye.
+
+use std::collections::BTreeMap;
+
+use crate::pieces::goban::{ChainIdx, Goban};
+use crate::pieces::stones::Color;
+use crate::pieces::util::coord::{corner_points, is_coord_valid, Coord};
+
+/// Chains of one color that are connected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    pub color: Color,
+    /// Indexes of the chains of the group, sorted.
+    pub chains: Vec<ChainIdx>,
+    pub stones: Vec<Coord>,
+    /// Number of distinct liberties of the chains.
+    pub liberties: usize,
+}
+
+/// An empty point where the opponent can cut a diagonal connection, because the other point
+/// between the two chains is already an opponent stone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CuttingPoint {
+    pub point: Coord,
+    /// The chains separated by the cut, the smallest index first.
+    pub chains: (ChainIdx, ChainIdx),
+}
+
+fn find(parents: &mut [ChainIdx], mut chain: ChainIdx) -> ChainIdx {
+    while parents[chain] != chain {
+        parents[chain] = parents[parents[chain]];
+        chain = parents[chain];
+    }
+    chain
+}
+
+fn ordered(a: ChainIdx, b: ChainIdx) -> (ChainIdx, ChainIdx) {
+    (a.min(b), a.max(b))
+}
+
+impl Goban {
+    /// Returns the pairs of chains of the same color that are linked.
+    fn chain_links(&self) -> Vec<(ChainIdx, ChainIdx)> {
+        let size = self.size();
+        let chain_at = |coord: Coord| self.chain_at(coord).map(|chain| chain.index());
+        let mut links = vec![];
+
+        // Diagonal connections and one point jumps.
+        for chain in self.chains() {
+            let color = chain.color();
+            for (line, column) in chain.stones() {
+                for corner in corner_points((line, column)) {
+                    let Some(other) = chain_at(corner) else {
+                        continue;
+                    };
+                    if other <= chain.index() || self.get_color(corner) != Some(color) {
+                        continue;
+                    }
+                    let opponents = [(line, corner.1), (corner.0, column)]
+                        .into_iter()
+                        .filter(|&point| self.get_color(point) == Some(!color))
+                        .count();
+                    if opponents < 2 {
+                        links.push((chain.index(), other));
+                    }
+                }
+                for (jump, middle) in [
+                    ((line + 2, column), (line + 1, column)),
+                    ((line, column + 2), (line, column + 1)),
+                ] {
+                    if !is_coord_valid(size, jump) || self.get_color(middle).is_some() {
+                        continue;
+                    }
+                    if let Some(other) = chain_at(jump) {
+                        if other != chain.index() && self.get_color(jump) == Some(color) {
+                            links.push(ordered(chain.index(), other));
+                        }
+                    }
+                }
+            }
+        }
+
+        // Shared liberties and shared eyes.
+        let mut shared_liberties = BTreeMap::<(ChainIdx, ChainIdx), u32>::new();
+        for point in self.get_empty_coords() {
+            let mut neighbors: Vec<ChainIdx> = self.get_neighbors_chain_indexes(point).collect();
+            neighbors.sort_unstable();
+            neighbors.dedup();
+            let mut colors = self.get_neighbors_points(point).map(|p| p.color);
+            let first = colors.next().flatten();
+            let is_eye = first.is_some() && colors.all(|color| color == first);
+            for (i, &a) in neighbors.iter().enumerate() {
+                for &b in &neighbors[i + 1..] {
+                    if self.get_chain(a).color != self.get_chain(b).color {
+                        continue;
+                    }
+                    if is_eye {
+                        links.push((a, b));
+                    }
+                    *shared_liberties.entry((a, b)).or_default() += 1;
+                }
+            }
+        }
+        links.extend(
+            shared_liberties
+                .into_iter()
+                .filter(|&(_, shared)| shared >= 2)
+                .map(|(pair, _)| pair),
+        );
+        links
+    }
+
+    /// Returns the groups of the goban, ordered by their first chain.
+    /// # Example
+    /// ```
+    /// use goban::pieces::goban::Goban;
+    ///
+    /// let goban: Goban = "
+    ///     . . . . .
+    ///     . X . X .
+    ///     . . X . .
+    ///     . . . . O
+    /// ".parse().unwrap();
+    /// let groups = goban.groups();
+    /// assert_eq!(groups.len(), 2);
+    /// assert_eq!(groups[0].stones.len(), 3);
+    /// ```
+    pub fn groups(&self) -> Vec<Group> {
+        let mut parents: Vec<ChainIdx> = (0..self.chains.len()).collect();
+        for (a, b) in self.chain_links() {
+            let (root_a, root_b) = (find(&mut parents, a), find(&mut parents, b));
+            let (root, child) = ordered(root_a, root_b);
+            parents[child] = root;
+        }
+
+        let mut groups = BTreeMap::<ChainIdx, Group>::new();
+        let mut liberties = BTreeMap::<ChainIdx, Vec<Coord>>::new();
+        for chain in self.chains() {
+            let root = find(&mut parents, chain.index());
+            let group = groups.entry(root).or_insert_with(|| Group {
+                color: chain.color(),
+                chains: vec![],
+                stones: vec![],
+                liberties: 0,
+            });
+            group.chains.push(chain.index());
+            group.stones.extend(chain.stones());
+            liberties.entry(root).or_default().extend(chain.liberties());
+        }
+        for (root, mut group_liberties) in liberties {
+            group_liberties.sort_unstable();
+            group_liberties.dedup();
+            groups.get_mut(&root).unwrap().liberties = group_liberties.len();
+        }
+        groups.into_values().collect()
+    }
+
+    /// Returns for each chain the chains of the opposite color touching it.
+    pub fn chain_adjacency(&self) -> BTreeMap<ChainIdx, Vec<ChainIdx>> {
+        self.chains()
+            .map(|chain| {
+                let enemies = chain
+                    .adjacent_enemies()
+                    .map(|enemy| enemy.index())
+                    .collect();
+                (chain.index(), enemies)
+            })
+            .collect()
+    }
+
+    /// Returns the cutting points of the diagonal connections, sorted.
+    /// # Example
+    /// ```
+    /// use goban::pieces::goban::Goban;
+    ///
+    /// let goban: Goban = "
+    ///     . . . .
+    ///     . X O .
+    ///     . . X .
+    ///     . . . .
+    /// ".parse().unwrap();
+    /// let cuts = goban.cutting_points();
+    /// assert_eq!(cuts.len(), 1);
+    /// assert_eq!(cuts[0].point, (2, 1));
+    /// ```
+    pub fn cutting_points(&self) -> Vec<CuttingPoint> {
+        let mut cuts = vec![];
+        for chain in self.chains() {
+            let color = chain.color();
+            for (line, column) in chain.stones() {
+                for corner in corner_points((line, column)) {
+                    let Some(other) = self.chain_at(corner) else {
+                        continue;
+                    };
+                    if other.index() <= chain.index() || other.color() != color {
+                        continue;
+                    }
+                    let crossing = [(line, corner.1), (corner.0, column)];
+                    let [first, second] = crossing.map(|point| self.get_color(point));
+                    let point = match (first, second) {
+                        (None, Some(c)) if c != color => crossing[0],
+                        (Some(c), None) if c != color => crossing[1],
+                        _ => continue,
+                    };
+                    cuts.push(CuttingPoint {
+                        point,
+                        chains: (chain.index(), other.index()),
+                    });
+                }
+            }
+        }
+        cuts.sort_unstable();
+        cuts.dedup();
+        cuts
+    }
+}
@@ -0,0 +1,127 @@
+//! Module for estimating the ownership of the intersections, with the Bouzy's 5/21 algorithm.
+//! Unlike the territory module it works in the middle of a game.
+
+use crate::pieces::goban::Goban;
+use crate::pieces::stones::Color;
+use crate::pieces::util::coord::{one_to_2dim, two_to_1dim};
+
+/// Value given to the stones before the dilations.
+const STONE_INFLUENCE: i32 = 128;
+const DILATIONS: u32 = 5;
+const EROSIONS: u32 = 21;
+
+impl Goban {
+    /// Applies one dilation of the Bouzy's algorithm. A point gains the number of neighbors
+    /// of its sign if it's not touched by a point of the opposite sign.
+    fn dilate(&self, values: &[i32]) -> Vec<i32> {
+        let mut res = values.to_vec();
+        for (idx, &value) in values.iter().enumerate() {
+            let (mut positives, mut negatives) = (0, 0);
+            for neighbor in self.neighbors_coords(one_to_2dim(self.size(), idx)) {
+                let neighbor_value = values[two_to_1dim(self.size(), neighbor)];
+                if neighbor_value > 0 {
+                    positives += 1;
+                } else if neighbor_value < 0 {
+                    negatives += 1;
+                }
+            }
+            if value >= 0 && negatives == 0 {
+                res[idx] += positives;
+            } else if value <= 0 && positives == 0 {
+                res[idx] -= negatives;
+            }
+        }
+        res
+    }
+
+    /// Applies one erosion of the Bouzy's algorithm. A point loses the number of neighbors
+    /// that don't have its sign, without changing of sign.
+    fn erode(&self, values: &[i32]) -> Vec<i32> {
+        let mut res = values.to_vec();
+        for (idx, &value) in values.iter().enumerate() {
+            if value == 0 {
+                continue;
+            }
+            let opposite_neighbors = self
+                .neighbors_coords(one_to_2dim(self.size(), idx))
+                .filter(|&neighbor| {
+                    let neighbor_value = values[two_to_1dim(self.size(), neighbor)];
+                    if value > 0 {
+                        neighbor_value <= 0
+                    } else {
+                        neighbor_value >= 0
+                    }
+                })
+                .count() as i32;
+            res[idx] = if value > 0 {
+                (value - opposite_neighbors).max(0)
+            } else {
+                (value + opposite_neighbors).min(0)
+            };
+        }
+        res
+    }
+
+    /// Returns the raw influence of the Bouzy's algorithm with the number of dilations and
+    /// erosions passed in parameter. The values are positive for Black and negative for White,
+    /// in a vector with a RowMajor Policy of the size of the goban.
+    pub fn bouzy_influence(&self, dilations: u32, erosions: u32) -> Vec<i32> {
+        let length = self.size().0 as usize * self.size().1 as usize;
+        let mut values: Vec<i32> = (0..length)
+            .map(|idx| match self.get_color(one_to_2dim(self.size(), idx)) {
+                Some(Color::Black) => STONE_INFLUENCE,
+                Some(Color::White) => -STONE_INFLUENCE,
+                None => 0,
+            })
+            .collect();
+        for _ in 0..dilations {
+            values = self.dilate(&values);
+        }
+        for _ in 0..erosions {
+            values = self.erode(&values);
+        }
+        values
+    }
+
+    /// Estimates the ownership of each intersection with the Bouzy's 5/21 algorithm.
+    /// Returns a vector with a RowMajor Policy where 1. is owned by Black, -1. owned by White
+    /// and 0. is neutral. Stones always belong to their color, the empty intersections are
+    /// graduated by the strength of the influence on them.
+    pub fn ownership_estimate(&self) -> Vec<f32> {
+        // An empty point can gain at most 4 per dilation.
+        let max_influence = (4 * DILATIONS) as f32;
+        self.bouzy_influence(DILATIONS, EROSIONS)
+            .into_iter()
+            .enumerate()
+            .map(
+                |(idx, value)| match self.get_color(one_to_2dim(self.size(), idx)) {
+                    Some(Color::Black) => 1.,
+                    Some(Color::White) => -1.,
+                    None => (value as f32 / max_influence).clamp(-1., 1.),
+                },
+            )
+            .collect()
+    }
+
+    ///
+    /// Estimates the territories with the ownership estimation, unlike
+    /// `calculate_territories` it can be used in the middle of a game.
+    ///
+    /// Returns (black territory, white territory)
+    ///
+    pub fn estimate_territories(&self) -> (usize, usize) {
+        self.ownership_estimate()
+            .into_iter()
+            .enumerate()
+            .filter(|&(idx, _)| self.get_color(one_to_2dim(self.size(), idx)).is_none())
+            .fold((0, 0), |(black, white), (_, value)| {
+                if value > 0. {
+                    (black + 1, white)
+                } else if value < 0. {
+                    (black, white + 1)
+                } else {
+                    (black, white)
+                }
+            })
+    }
+}
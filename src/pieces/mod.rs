@@ -5,8 +5,16 @@
 pub(super) type Nat = u8;
 pub(super) type BoardIdx = usize;
 
+#[cfg(feature = "bitboard")]
+pub mod bitboard;
+pub mod board;
 pub mod chain;
+pub mod chain_view;
+pub mod diagram;
 pub mod goban;
+pub mod groups;
+pub mod influence;
+pub mod sensei;
 pub mod stones;
 pub mod territory;
 pub mod util;
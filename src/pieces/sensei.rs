@@ -0,0 +1,380 @@
+//! Module for the diagrams of Sensei's Library, also used on the Go forums.
+//!
+//! Each line of the diagram starts with `$$`. The first line is the header, like
+//! `$$Wc9m21 Title`: the color of the move numbered 1, `c` to show the coordinates, the size
+//! of the goban, `m` and the number of the first move, then the title.
+//! The stones are `X` and `O`, the empty intersections `.` and the star points `,`. The moves
+//! are numbered from `1` to `0` (the tenth). The edges of the goban are the borders `|` and
+//! `---`, a diagram without all the edges is a part of the goban.
+//! The moves that can't be shown are in a line after the diagram, like "8 at 2, 9 pass".
+//! # Example
+//! ```
+//! use goban::pieces::goban::Goban;
+//! use goban::pieces::stones::Color;
+//!
+//! let goban = Goban::from_sensei(
+//!     "$$B 19x19 corner
+//!      $$ ----------
+//!      $$ . . . . . |
+//!      $$ . . O . . |
+//!      $$ . . . X . |
+//!      $$ . . . , . |",
+//! )
+//! .unwrap();
+//! assert_eq!(goban.size(), (19, 19));
+//! assert_eq!(goban.get_color((1, 16)), Some(Color::White));
+//! assert_eq!(goban.get_color((2, 17)), Some(Color::Black));
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::notation::Notation;
+use crate::pieces::board::Board;
+use crate::pieces::goban::Goban;
+use crate::pieces::stones::{Color, MaybeColor, EMPTY};
+use crate::pieces::util::coord::{two_to_1dim, Coord, Size};
+use crate::pieces::Nat;
+use crate::render::star_points;
+use crate::rules::game::Game;
+use crate::rules::JAPANESE;
+
+/// A position with up to ten numbered moves, read from or written to a diagram.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SenseiDiagram {
+    pub size: Size,
+    /// The color of the move numbered 1.
+    pub first_color: Color,
+    /// The number shown for the move 1 of the diagram.
+    pub first_move: u32,
+    /// Asks to show the coordinates.
+    pub coordinates: bool,
+    pub title: String,
+    /// Stones before the numbered moves, in a RowMajor Policy.
+    pub points: Vec<MaybeColor>,
+    /// The numbered moves, None for a pass.
+    pub moves: Vec<Option<Coord>>,
+}
+
+enum Cell {
+    Stone(Color),
+    Empty,
+    /// Number of the move in the diagram, from 1 to 10.
+    Move(usize),
+}
+
+fn parse_cell(c: char) -> Option<Cell> {
+    match c {
+        // The stones with a circle, a square, a triangle or a cross.
+        'X' | 'B' | '#' | 'Y' | 'Z' => Some(Cell::Stone(Color::Black)),
+        'O' | 'W' | '@' | 'Q' | 'P' => Some(Cell::Stone(Color::White)),
+        '.' | ',' | '_' | 'C' | 'S' | 'T' | 'M' | 'a'..='z' => Some(Cell::Empty),
+        '0' => Some(Cell::Move(10)),
+        '1'..='9' => Some(Cell::Move(c as usize - '0' as usize)),
+        _ => None,
+    }
+}
+
+/// Parses the header without the `$$`, ex: "Bc9m21 Title".
+fn parse_header(mut header: &str, diagram: &mut SenseiDiagram) -> Option<Nat> {
+    let number = |s: &str| -> (Option<u32>, usize) {
+        let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        (s[..end].parse().ok(), end)
+    };
+    if let Some(rest) = header.strip_prefix('B') {
+        header = rest;
+    } else if let Some(rest) = header.strip_prefix('W') {
+        diagram.first_color = Color::White;
+        header = rest;
+    }
+    if let Some(rest) = header.strip_prefix('c') {
+        diagram.coordinates = true;
+        header = rest;
+    }
+    let (size, end) = number(header);
+    header = &header[end..];
+    if let Some(rest) = header.strip_prefix('m') {
+        let (first_move, end) = number(rest);
+        diagram.first_move = first_move.unwrap_or(1);
+        header = &rest[end..];
+    }
+    diagram.title = header.trim().to_string();
+    size.map(|size| size as Nat)
+}
+
+/// Parses a line of notes like "8 at 2, 9 pass, 10 at D4". The numbers are the ones shown.
+fn parse_notes(line: &str, diagram: &SenseiDiagram) -> Vec<(u32, Option<Coord>)> {
+    let mut notes = vec![];
+    for note in line.split(',') {
+        let mut words = note.split_whitespace();
+        let (Some(number), Some(what)) = (words.next(), words.next()) else {
+            return vec![];
+        };
+        let Ok(number) = number.parse::<u32>() else {
+            return vec![];
+        };
+        let point = match (what, words.next(), words.next()) {
+            ("pass", None, _) => None,
+            ("at", Some(point), None) => match point.parse::<u32>() {
+                Ok(shown) => {
+                    let index = shown.wrapping_sub(diagram.first_move) as usize;
+                    match diagram.moves.get(index) {
+                        Some(&Some(coord)) => Some(coord),
+                        _ => return vec![],
+                    }
+                }
+                Err(_) => match Notation::Gtp.parse_coord(diagram.size, point) {
+                    Ok(coord) => Some(coord),
+                    Err(_) => return vec![],
+                },
+            },
+            _ => return vec![],
+        };
+        notes.push((number, point));
+    }
+    notes
+}
+
+impl FromStr for SenseiDiagram {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut diagram = SenseiDiagram {
+            size: (19, 19),
+            first_color: Color::Black,
+            first_move: 1,
+            coordinates: false,
+            title: String::new(),
+            points: vec![],
+            moves: vec![],
+        };
+        let mut lines = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with("$$"));
+        let header = lines.next().ok_or("The diagram doesn't have a $$ header")?;
+        let size = parse_header(&header[2..], &mut diagram);
+
+        // The rows with the edges on the (top, bottom, left, right).
+        let mut rows: Vec<Vec<Cell>> = vec![];
+        let mut edges = (false, false, false, false);
+        for line in lines {
+            let row: String = line[2..].chars().filter(|c| !c.is_whitespace()).collect();
+            if row.is_empty() {
+                continue;
+            }
+            if row.contains('-') && row.chars().all(|c| matches!(c, '-' | '+' | '|')) {
+                if rows.is_empty() {
+                    edges.0 = true;
+                } else {
+                    edges.1 = true;
+                }
+                continue;
+            }
+            let mut cells = row.as_str();
+            if let Some(rest) = cells.strip_prefix('|') {
+                edges.2 = true;
+                cells = rest;
+            }
+            if let Some(rest) = cells.strip_suffix('|') {
+                edges.3 = true;
+                cells = rest;
+            }
+            let cells = cells
+                .chars()
+                .map(parse_cell)
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| format!("Invalid line in the diagram: {line}"))?;
+            if rows.first().is_some_and(|first| first.len() != cells.len()) {
+                return Err(format!(
+                    "The line {line} doesn't have the size of the others"
+                ));
+            }
+            rows.push(cells);
+        }
+        let height = rows.len() as Nat;
+        let width = rows.first().ok_or("The diagram is empty")?.len() as Nat;
+
+        diagram.size = match size {
+            Some(size) => (size, size),
+            None if edges == (true, true, true, true) => (height, width),
+            None => (19, 19),
+        };
+        let (goban_height, goban_width) = diagram.size;
+        if !(1..=19).contains(&goban_height) || !(1..=19).contains(&goban_width) {
+            return Err(format!(
+                "Invalid size of goban: {goban_height}x{goban_width}"
+            ));
+        }
+        if height > goban_height || width > goban_width {
+            return Err(format!(
+                "The diagram is bigger than the goban: {height}x{width}"
+            ));
+        }
+        // Without the top edge the diagram is at the bottom if the bottom edge is shown.
+        let origin = (
+            if !edges.0 && edges.1 {
+                goban_height - height
+            } else {
+                0
+            },
+            if !edges.2 && edges.3 {
+                goban_width - width
+            } else {
+                0
+            },
+        );
+
+        diagram.points = vec![EMPTY; goban_height as usize * goban_width as usize];
+        for (line, row) in rows.into_iter().enumerate() {
+            for (column, cell) in row.into_iter().enumerate() {
+                let coord = (origin.0 + line as Nat, origin.1 + column as Nat);
+                match cell {
+                    Cell::Stone(color) => {
+                        diagram.points[two_to_1dim(diagram.size, coord)] = Some(color)
+                    }
+                    Cell::Empty => (),
+                    Cell::Move(number) => {
+                        if diagram.moves.len() < number {
+                            diagram.moves.resize(number, None);
+                        }
+                        if diagram.moves[number - 1].replace(coord).is_some() {
+                            return Err(format!("The move {number} is twice in the diagram"));
+                        }
+                    }
+                }
+            }
+        }
+
+        let notes: Vec<_> = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.starts_with("$$"))
+            .flat_map(|line| parse_notes(line, &diagram))
+            .collect();
+        for (number, point) in notes {
+            let index = number.wrapping_sub(diagram.first_move) as usize;
+            if index >= 10 {
+                return Err(format!("The move {number} isn't in the diagram"));
+            }
+            if diagram.moves.len() <= index {
+                diagram.moves.resize(index + 1, None);
+            }
+            diagram.moves[index] = point;
+        }
+        Ok(diagram)
+    }
+}
+
+/// Writes the whole goban with its edges, and the notes of the moves that can't be shown.
+impl Display for SenseiDiagram {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (height, width) = self.size;
+        let mut cells: Vec<char> = self
+            .points
+            .iter()
+            .map(|point| match point {
+                Some(Color::Black) => 'X',
+                Some(Color::White) => 'O',
+                None => '.',
+            })
+            .collect();
+        for star in star_points(self.size) {
+            let cell = &mut cells[two_to_1dim(self.size, star)];
+            if *cell == '.' {
+                *cell = ',';
+            }
+        }
+        let mut notes = vec![];
+        for (index, &point) in self.moves.iter().enumerate() {
+            let number = self.first_move + index as u32;
+            let Some(coord) = point else {
+                notes.push(format!("{number} pass"));
+                continue;
+            };
+            let cell = &mut cells[two_to_1dim(self.size, coord)];
+            match *cell {
+                '.' | ',' => *cell = char::from_digit((index as u32 + 1) % 10, 10).unwrap(),
+                'X' | 'O' => notes.push(format!(
+                    "{number} at {}",
+                    Notation::Gtp
+                        .format_coord(self.size, coord)
+                        .expect("The moves are played on the goban")
+                )),
+                digit => {
+                    let shown = (digit as u32 - '0' as u32 + 9) % 10 + 1;
+                    notes.push(format!("{number} at {}", self.first_move + shown - 1));
+                }
+            }
+        }
+
+        write!(
+            f,
+            "$${}",
+            if self.first_color == Color::Black {
+                'B'
+            } else {
+                'W'
+            }
+        )?;
+        if self.coordinates {
+            write!(f, "c")?;
+        }
+        if height == width {
+            write!(f, "{height}")?;
+        }
+        if self.first_move != 1 {
+            write!(f, "m{}", self.first_move)?;
+        }
+        if !self.title.is_empty() {
+            write!(f, " {}", self.title)?;
+        }
+        let border = format!("$$ +{}+", "-".repeat(2 * width as usize + 1));
+        writeln!(f)?;
+        writeln!(f, "{border}")?;
+        for row in cells.chunks(width as usize) {
+            write!(f, "$$ |")?;
+            for c in row {
+                write!(f, " {c}")?;
+            }
+            writeln!(f, " |")?;
+        }
+        writeln!(f, "{border}")?;
+        if !notes.is_empty() {
+            writeln!(f, "{}", notes.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl SenseiDiagram {
+    /// Creates the diagram of the goban without numbered moves.
+    pub fn from_goban<B: Board>(goban: &B, first_color: Color) -> Self {
+        let (height, width) = goban.size();
+        let mut points = goban.to_vec();
+        points.truncate(height as usize * width as usize);
+        SenseiDiagram {
+            size: goban.size(),
+            first_color,
+            first_move: 1,
+            coordinates: false,
+            title: String::new(),
+            points,
+            moves: vec![],
+        }
+    }
+}
+
+impl Goban {
+    /// Returns the Sensei's Library diagram of the goban.
+    pub fn to_sensei(&self) -> String {
+        SenseiDiagram::from_goban(self, Color::Black).to_string()
+    }
+
+    /// Reads a Sensei's Library diagram, the numbered moves are played with the captures.
+    /// # Errors
+    /// If the diagram is invalid, or if a numbered move is illegal.
+    pub fn from_sensei(diagram: &str) -> Result<Goban, String> {
+        Game::from_sensei(diagram, JAPANESE).map(|game| *game.goban())
+    }
+}
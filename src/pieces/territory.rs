@@ -2,9 +2,24 @@
 
 use std::collections::HashSet;
 
-use crate::pieces::goban::Goban;
+use crate::pieces::goban::{ChainIdx, Goban};
 use crate::pieces::stones::{Color, EMPTY};
 use crate::pieces::stones::Point;
+use crate::pieces::util::coord::{one_to_2dim, two_to_1dim, Coord};
+
+/// What an intersection is at the end of a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ownership {
+    /// A living stone.
+    Stone(Color),
+    /// An empty point, or a dead stone, surrounded by one color.
+    Territory(Color),
+    /// A neutral point.
+    Dame,
+    /// A neutral point shared by chains in seki, neither player can fill it without being
+    /// captured.
+    SekiLiberty,
+}
 
 impl Goban {
     #[inline]
@@ -96,9 +111,9 @@ impl Goban {
                 }
             }
             if neutral.0 && !neutral.1 {
-                black_territory.extend(empty_chain.into_iter())
+                black_territory.extend(empty_chain)
             } else if !neutral.0 && neutral.1 {
-                white_territory.extend(empty_chain.into_iter())
+                white_territory.extend(empty_chain)
             }
         }
         (black_territory.into_iter(), white_territory.into_iter())
@@ -115,4 +130,98 @@ impl Goban {
         let (black_territory, white_territory) = self.get_territories();
         (black_territory.count(), white_territory.count())
     }
+
+    /// Returns what each intersection is, in a RowMajor Policy.
+    /// The chains with a stone in `dead_stones` are dead, they become the territory of the
+    /// opponent if it surrounds them.
+    /// # Example
+    /// ```
+    /// use goban::pieces::goban::Goban;
+    /// use goban::pieces::stones::Color;
+    /// use goban::pieces::territory::Ownership;
+    ///
+    /// let goban: Goban = "
+    ///     . X O .
+    ///     . X O X
+    ///     . X O .
+    /// ".parse().unwrap();
+    /// let ownership = goban.ownership_map(&[(1, 3)]);
+    /// assert_eq!(ownership[0], Ownership::Territory(Color::Black));
+    /// assert_eq!(ownership[1], Ownership::Stone(Color::Black));
+    /// assert_eq!(ownership[7], Ownership::Territory(Color::White));
+    /// ```
+    pub fn ownership_map(&self, dead_stones: &[Coord]) -> Vec<Ownership> {
+        let size = self.size();
+        let length = size.0 as usize * size.1 as usize;
+        let mut goban = *self;
+        let mut dead_chains: Vec<ChainIdx> = dead_stones
+            .iter()
+            .filter_map(|&coord| self.chain_at(coord).map(|chain| chain.index()))
+            .collect();
+        dead_chains.sort_unstable();
+        dead_chains.dedup();
+        for chain_idx in dead_chains {
+            goban.remove_chain(chain_idx);
+        }
+
+        let mut ownership = vec![Ownership::Dame; length];
+        let mut explored = vec![false; length];
+        for start in 0..length {
+            let start_coord = one_to_2dim(size, start);
+            if let Some(color) = goban.get_color(start_coord) {
+                ownership[start] = Ownership::Stone(color);
+                continue;
+            }
+            if explored[start] {
+                continue;
+            }
+            // Flood fill of the empty region.
+            explored[start] = true;
+            let mut region = vec![start_coord];
+            let mut to_explore = vec![start_coord];
+            let (mut black_border, mut white_border) = (false, false);
+            while let Some(coord) = to_explore.pop() {
+                for point in goban.get_neighbors_points(coord) {
+                    match point.color {
+                        Some(Color::Black) => black_border = true,
+                        Some(Color::White) => white_border = true,
+                        None => {
+                            let idx = two_to_1dim(size, point.coord);
+                            if !explored[idx] {
+                                explored[idx] = true;
+                                region.push(point.coord);
+                                to_explore.push(point.coord);
+                            }
+                        }
+                    }
+                }
+            }
+            for coord in region {
+                ownership[two_to_1dim(size, coord)] = match (black_border, white_border) {
+                    (true, false) => Ownership::Territory(Color::Black),
+                    (false, true) => Ownership::Territory(Color::White),
+                    (true, true) if goban.is_seki_liberty(coord) => Ownership::SekiLiberty,
+                    _ => Ownership::Dame,
+                };
+            }
+        }
+        ownership
+    }
+
+    /// Returns true if the point touches the two colors and playing it would be a self atari
+    /// without capture for both.
+    fn is_seki_liberty(&self, coord: Coord) -> bool {
+        let touches = |color: Color| {
+            self.get_neighbors_points(coord)
+                .any(|point| point.color == Some(color))
+        };
+        let self_atari = |color: Color| {
+            let mut goban = *self;
+            let (captured, chain_idx) = goban.push_wth_feedback(coord, color);
+            captured.is_empty() && goban.get_chain(chain_idx).number_of_liberties() <= 1
+        };
+        [Color::Black, Color::White]
+            .into_iter()
+            .all(|color| touches(color) && self_atari(color))
+    }
 }
@@ -51,18 +51,18 @@ pub mod coord {
 
     #[inline(always)]
     pub const fn two_to_1dim(size: Size, point: Coord) -> usize {
-        (point.0 as u32 * size.0 as u32 + point.1 as u32) as usize
+        (point.0 as u32 * size.1 as u32 + point.1 as u32) as usize
     }
 
     #[inline(always)]
     pub const fn one_to_2dim(size: Size, index: usize) -> Coord {
-        ((index / size.0 as usize) as u8, (index % size.1 as usize) as u8)
+        ((index / size.1 as usize) as u8, (index % size.1 as usize) as u8)
     }
 
     #[macro_export]
     macro_rules! one2dim {
         ($size: expr, $index: expr) => {
-            (($index / $size.0 as usize)  as u8, ($index % $size.1 as usize) as u8)
+            (($index / $size.1 as usize)  as u8, ($index % $size.1 as usize) as u8)
         };
     }
 
@@ -0,0 +1,206 @@
+//! Module for printing a game record (kifu) like in the game collections: the game is split in
+//! figures of some moves, the stones of the figure are numbered on the goban, and the moves
+//! played on a point already used in the figure are in the notes, like "12 at 5".
+//! # Example
+//! ```
+//! use goban::render::kifu::KifuPrinter;
+//! use goban::rules::game::Game;
+//! use goban::rules::{GobanSizes, Move, JAPANESE};
+//!
+//! let mut game = Game::new(GobanSizes::Custom(4, 4), JAPANESE);
+//! for m in [(0, 1), (0, 2), (1, 0), (1, 1), (3, 3), (0, 0)] {
+//!     game.play(Move::Play(m.0, m.1));
+//! }
+//! game.play(Move::Play(1, 2)).play(Move::Pass).play(Move::Play(0, 1));
+//! let kifu = KifuPrinter::new().moves_per_figure(5).render(&game);
+//! assert_eq!(
+//!     kifu,
+//!     "Figure 1: moves 1 to 5, Black first
+//!    A B C D
+//!  4 . 1 2 . 4
+//!  3 3 4 . . 3
+//!  2 . . . . 2
+//!  1 . . . 5 1
+//!    A B C D
+//!
+//! Figure 2: moves 6 to 9, White first
+//!    A B C D
+//!  4 6 X O . 4
+//!  3 X O 7 . 3
+//!  2 . . . . 2
+//!  1 . . . X 1
+//!    A B C D
+//! 8 pass, 9 at B4
+//! "
+//! );
+//! ```
+
+use std::fmt::Write;
+
+use crate::notation::{gtp_column, Notation};
+use crate::pieces::goban::Goban;
+use crate::pieces::stones::Color;
+use crate::pieces::util::coord::two_to_1dim;
+use crate::render::star_points;
+use crate::rules::game::Game;
+use crate::rules::Move;
+
+/// A diagram of the game record, with the moves numbered from the start of the game.
+#[derive(Debug, Clone)]
+pub struct Figure {
+    /// The number of the figure, counted from 1.
+    pub number: usize,
+    pub first_move: u32,
+    pub last_move: u32,
+    /// The color who plays the first move of the figure.
+    pub first_color: Color,
+    /// The goban before the first move of the figure.
+    pub goban: Goban,
+    /// In a RowMajor Policy, the moves of the figure shown on the goban.
+    pub numbers: Vec<Option<u32>>,
+    /// The moves that can't be shown on the goban, like "12 at 5", "14 pass" or "17 at D4"
+    /// when a stone of a previous figure was captured.
+    pub notes: Vec<String>,
+}
+
+/// Options of the game record.
+#[derive(Debug, Clone)]
+pub struct KifuPrinter {
+    moves_per_figure: u32,
+    coordinates: bool,
+}
+
+impl KifuPrinter {
+    /// Creates a printer with figures of 100 moves and the coordinates.
+    pub fn new() -> Self {
+        KifuPrinter {
+            moves_per_figure: 100,
+            coordinates: true,
+        }
+    }
+
+    /// Number of moves of each figure, passes included. At least one.
+    pub fn moves_per_figure(&mut self, moves: u32) -> &mut Self {
+        self.moves_per_figure = moves.max(1);
+        self
+    }
+
+    /// Prints the GTP coordinates around the figures.
+    pub fn coordinates(&mut self, coordinates: bool) -> &mut Self {
+        self.coordinates = coordinates;
+        self
+    }
+
+    /// Splits the game in figures, the resignation isn't a move of the figures.
+    pub fn figures(&self, game: &Game) -> Vec<Figure> {
+        let size = game.size();
+        let moves: Vec<(Color, Move)> = game
+            .moves()
+            .iter()
+            .copied()
+            .take_while(|(_, m)| !matches!(m, Move::Resign(_)))
+            .collect();
+
+        let mut figures = vec![];
+        for (index, chunk) in moves.chunks(self.moves_per_figure as usize).enumerate() {
+            let first_move = index as u32 * self.moves_per_figure + 1;
+            let goban = game
+                .position_at(first_move as usize - 1)
+                .expect("The figure starts before the last move");
+            let mut numbers = vec![None; size.0 as usize * size.1 as usize];
+            let mut notes = vec![];
+            for (number, &(_, m)) in (first_move..).zip(chunk) {
+                if let Move::Play(line, column) = m {
+                    let point = two_to_1dim(size, (line, column));
+                    match numbers[point] {
+                        Some(previous) => notes.push(format!("{number} at {previous}")),
+                        None if goban.get_color((line, column)).is_some() => notes.push(format!(
+                            "{number} at {}",
+                            Notation::Gtp
+                                .format_move(size, m)
+                                .expect("The moves are played on the goban")
+                        )),
+                        None => numbers[point] = Some(number),
+                    }
+                } else {
+                    notes.push(format!("{number} pass"));
+                }
+            }
+            figures.push(Figure {
+                number: index + 1,
+                first_move,
+                last_move: first_move + chunk.len() as u32 - 1,
+                first_color: chunk[0].0,
+                goban,
+                numbers,
+                notes,
+            });
+        }
+        figures
+    }
+
+    /// Renders a figure, the stones of the previous figures are `X` and `O`.
+    pub fn render_figure(&self, figure: &Figure) -> String {
+        let goban = &figure.goban;
+        let (height, width) = goban.size();
+        let cell = figure.last_move.to_string().len() + 1;
+        let stars = star_points(goban.size());
+        let mut buff = format!(
+            "Figure {}: moves {} to {}, {} first\n",
+            figure.number, figure.first_move, figure.last_move, figure.first_color
+        );
+
+        let mut labels = String::from("  ");
+        for column in 0..width {
+            let _ = write!(labels, "{:>cell$}", gtp_column(column));
+        }
+        labels.push('\n');
+        if self.coordinates {
+            buff.push_str(&labels);
+        }
+        for line in 0..height {
+            if self.coordinates {
+                let _ = write!(buff, "{:>2}", height - line);
+            }
+            for column in 0..width {
+                let coord = (line, column);
+                let number = figure.numbers[two_to_1dim(goban.size(), coord)];
+                let text = match (number, goban.get_color(coord)) {
+                    (Some(number), _) => number.to_string(),
+                    (None, Some(Color::Black)) => "X".to_string(),
+                    (None, Some(Color::White)) => "O".to_string(),
+                    (None, None) if stars.contains(&coord) => "+".to_string(),
+                    (None, None) => ".".to_string(),
+                };
+                let _ = write!(buff, "{text:>cell$}");
+            }
+            if self.coordinates {
+                let _ = write!(buff, " {}", height - line);
+            }
+            buff.push('\n');
+        }
+        if self.coordinates {
+            buff.push_str(&labels);
+        }
+        if !figure.notes.is_empty() {
+            buff.push_str(&figure.notes.join(", "));
+            buff.push('\n');
+        }
+        buff
+    }
+
+    /// Renders all the figures of the game, separated by an empty line.
+    pub fn render(&self, game: &Game) -> String {
+        self.figures(game)
+            .iter()
+            .map(|figure| self.render_figure(figure))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Default for KifuPrinter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,51 @@
+//! Module for drawing gobans and games, in SVG for the documents and the web, or in text
+//! for the terminals, and the numbered diagrams of the game records.
+
+use crate::pieces::util::coord::{Coord, Size};
+use crate::rules::game::Game;
+use crate::rules::Move;
+
+pub mod kifu;
+pub mod svg;
+pub mod terminal;
+
+/// Returns the star points (hoshi) of a goban. The corner points are on the fourth line for
+/// the sides of 13 and bigger, else on the third line. The center is added when the sides are
+/// odd, and the middle of the sides when they are 15 or bigger.
+pub fn star_points((height, width): Size) -> Vec<Coord> {
+    let axis = |side: u8| {
+        let edge = if side >= 13 { 3 } else { 2 };
+        if side < 7 {
+            (vec![], None)
+        } else if side % 2 == 1 {
+            (vec![edge, side - 1 - edge], Some(side / 2))
+        } else {
+            (vec![edge, side - 1 - edge], None)
+        }
+    };
+    let (corner_lines, middle_line) = axis(height);
+    let (corner_columns, middle_column) = axis(width);
+    let mut points = vec![];
+    for &line in &corner_lines {
+        for &column in &corner_columns {
+            points.push((line, column));
+        }
+    }
+    if let (Some(line), Some(column)) = (middle_line, middle_column) {
+        points.push((line, column));
+        if height >= 15 && width >= 15 {
+            points.extend(corner_columns.iter().map(|&column| (line, column)));
+            points.extend(corner_lines.iter().map(|&l| (l, column)));
+        }
+    }
+    points.sort_unstable();
+    points
+}
+
+/// Returns the last stone played in the game.
+pub(crate) fn last_stone(game: &Game) -> Option<Coord> {
+    match game.moves().last() {
+        Some(&(_, Move::Play(line, column))) => Some((line, column)),
+        _ => None,
+    }
+}
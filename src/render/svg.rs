@@ -0,0 +1,467 @@
+//! Module for rendering a goban or a game in SVG.
+//! # Example
+//! ```
+//! use goban::render::svg::{Markup, SvgRenderer};
+//! use goban::rules::game::Game;
+//! use goban::rules::{GobanSizes, Move, CHINESE};
+//!
+//! let mut game = Game::new(GobanSizes::Nine, CHINESE);
+//! game.play(Move::Play(2, 6)).play(Move::Play(6, 2));
+//! let svg = SvgRenderer::new()
+//!     .move_numbers(true)
+//!     .markup((4, 4), Markup::Label("A".to_string()))
+//!     .render_game(&game);
+//! assert!(svg.starts_with("<svg"));
+//! ```
+
+use std::fmt::Write;
+
+use crate::notation::gtp_column;
+use crate::pieces::goban::Goban;
+use crate::pieces::stones::Color;
+use crate::pieces::territory::Ownership;
+use crate::pieces::util::coord::{is_coord_valid, one_to_2dim, two_to_1dim, Coord};
+use crate::render::{last_stone, star_points};
+use crate::rules::game::Game;
+
+/// Marks drawn on the intersections, like the SGF properties TR, SQ, CR, MA and LB.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Markup {
+    Triangle,
+    Square,
+    Circle,
+    Cross,
+    Label(String),
+}
+
+/// Colors of the drawing, in any format understood by SVG.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    pub background: String,
+    pub lines: String,
+    pub black: String,
+    pub white: String,
+    /// Border of the stones.
+    pub stones_border: String,
+}
+
+impl Theme {
+    /// Light wood with black lines.
+    pub fn wood() -> Self {
+        Theme {
+            background: "#dcb35c".to_string(),
+            lines: "#000000".to_string(),
+            black: "#000000".to_string(),
+            white: "#ffffff".to_string(),
+            stones_border: "#000000".to_string(),
+        }
+    }
+
+    /// Black and white, for the printed documents.
+    pub fn print() -> Self {
+        Theme {
+            background: "#ffffff".to_string(),
+            ..Theme::wood()
+        }
+    }
+
+    /// Dark background with light lines.
+    pub fn dark() -> Self {
+        Theme {
+            background: "#2b2b2b".to_string(),
+            lines: "#a0a0a0".to_string(),
+            black: "#111111".to_string(),
+            white: "#e8e8e8".to_string(),
+            stones_border: "#a0a0a0".to_string(),
+        }
+    }
+
+    /// The same theme with the colors escaped, they are written in the attributes.
+    fn escaped(&self) -> Theme {
+        Theme {
+            background: escape_xml(&self.background),
+            lines: escape_xml(&self.lines),
+            black: escape_xml(&self.black),
+            white: escape_xml(&self.white),
+            stones_border: escape_xml(&self.stones_border),
+        }
+    }
+
+    /// Returns the color of the marks drawn on the intersection.
+    fn mark_color(&self, goban: &Goban, coord: Coord) -> &str {
+        match goban.get_color(coord) {
+            Some(Color::Black) => &self.white,
+            Some(Color::White) => &self.black,
+            None => &self.lines,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::wood()
+    }
+}
+
+/// Options of the SVG rendering.
+#[derive(Debug, Clone)]
+pub struct SvgRenderer {
+    cell_size: f32,
+    coordinates: bool,
+    star_points: bool,
+    last_move: bool,
+    move_numbers: bool,
+    ko: bool,
+    territory: Option<Vec<Coord>>,
+    markup: Vec<(Coord, Markup)>,
+    theme: Theme,
+}
+
+impl SvgRenderer {
+    /// Creates a renderer with the coordinates, the star points, the last move and the ko.
+    pub fn new() -> Self {
+        SvgRenderer {
+            cell_size: 30.,
+            coordinates: true,
+            star_points: true,
+            last_move: true,
+            move_numbers: false,
+            ko: true,
+            territory: None,
+            markup: vec![],
+            theme: Theme::default(),
+        }
+    }
+
+    /// The distance between two lines, 30 by default.
+    pub fn cell_size(&mut self, cell_size: f32) -> &mut Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    /// Draws the GTP coordinates around the goban.
+    pub fn coordinates(&mut self, coordinates: bool) -> &mut Self {
+        self.coordinates = coordinates;
+        self
+    }
+
+    pub fn star_points(&mut self, star_points: bool) -> &mut Self {
+        self.star_points = star_points;
+        self
+    }
+
+    /// Marks the last stone played, only for the games.
+    pub fn last_move(&mut self, last_move: bool) -> &mut Self {
+        self.last_move = last_move;
+        self
+    }
+
+    /// Writes the move numbers on the stones, only for the games.
+    pub fn move_numbers(&mut self, move_numbers: bool) -> &mut Self {
+        self.move_numbers = move_numbers;
+        self
+    }
+
+    /// Marks the ko point, only for the games.
+    pub fn ko(&mut self, ko: bool) -> &mut Self {
+        self.ko = ko;
+        self
+    }
+
+    /// Draws the territories, the chains with a stone in `dead_stones` are dead.
+    pub fn territory(&mut self, dead_stones: &[Coord]) -> &mut Self {
+        self.territory = Some(dead_stones.to_vec());
+        self
+    }
+
+    /// Adds a mark on an intersection.
+    pub fn markup(&mut self, coord: Coord, markup: Markup) -> &mut Self {
+        self.markup.push((coord, markup));
+        self
+    }
+
+    pub fn theme(&mut self, theme: Theme) -> &mut Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Renders the goban alone.
+    pub fn render_goban(&self, goban: &Goban) -> String {
+        self.render(goban, None, None, None)
+    }
+
+    /// Renders the goban of the game with the last move, the move numbers and the ko.
+    pub fn render_game(&self, game: &Game) -> String {
+        self.render(
+            game.goban(),
+            last_stone(game).filter(|_| self.last_move),
+            self.move_numbers.then(|| game.move_numbers()),
+            game.ko_point().filter(|_| self.ko),
+        )
+    }
+
+    fn margin(&self) -> f32 {
+        if self.coordinates {
+            self.cell_size * 1.5
+        } else {
+            self.cell_size
+        }
+    }
+
+    /// Returns the position of the intersection in the image.
+    fn position(&self, (line, column): Coord) -> (f32, f32) {
+        (
+            self.margin() + column as f32 * self.cell_size,
+            self.margin() + line as f32 * self.cell_size,
+        )
+    }
+
+    fn render(
+        &self,
+        goban: &Goban,
+        last_move: Option<Coord>,
+        numbers: Option<Vec<Option<u32>>>,
+        ko: Option<Coord>,
+    ) -> String {
+        let (height, width) = goban.size();
+        let cell = self.cell_size;
+        let margin = self.margin();
+        let theme = &self.theme.escaped();
+        let image_width = 2. * margin + (width as f32 - 1.) * cell;
+        let image_height = 2. * margin + (height as f32 - 1.) * cell;
+        let font_size = cell * 0.45;
+        let mut svg = String::new();
+
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{image_width}" height="{image_height}" viewBox="0 0 {image_width} {image_height}">"#
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect width="{image_width}" height="{image_height}" fill="{}"/>"#,
+            theme.background
+        );
+
+        // Lines
+        for line in 0..height {
+            let (x1, y) = self.position((line, 0));
+            let (x2, _) = self.position((line, width - 1));
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{x1}" y1="{y}" x2="{x2}" y2="{y}" stroke="{}"/>"#,
+                theme.lines
+            );
+        }
+        for column in 0..width {
+            let (x, y1) = self.position((0, column));
+            let (_, y2) = self.position((height - 1, column));
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{x}" y1="{y1}" x2="{x}" y2="{y2}" stroke="{}"/>"#,
+                theme.lines
+            );
+        }
+
+        if self.star_points {
+            for point in star_points(goban.size()) {
+                let (x, y) = self.position(point);
+                let _ = writeln!(
+                    svg,
+                    r#"<circle cx="{x}" cy="{y}" r="{}" fill="{}"/>"#,
+                    cell * 0.1,
+                    theme.lines
+                );
+            }
+        }
+
+        if self.coordinates {
+            let text = |svg: &mut String, x: f32, y: f32, label: &str| {
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{x}" y="{y}" font-family="sans-serif" font-size="{font_size}" text-anchor="middle" dominant-baseline="central" fill="{}">{label}</text>"#,
+                    theme.lines
+                );
+            };
+            for column in 0..width {
+                let (x, _) = self.position((0, column));
+                let label = gtp_column(column).to_string();
+                text(&mut svg, x, margin - cell, &label);
+                text(&mut svg, x, image_height - margin + cell, &label);
+            }
+            for line in 0..height {
+                let (_, y) = self.position((line, 0));
+                let label = (height - line).to_string();
+                text(&mut svg, margin - cell, y, &label);
+                text(&mut svg, image_width - margin + cell, y, &label);
+            }
+        }
+
+        // Stones
+        for line in 0..height {
+            for column in 0..width {
+                let Some(color) = goban.get_color((line, column)) else {
+                    continue;
+                };
+                let (x, y) = self.position((line, column));
+                let fill = match color {
+                    Color::Black => &theme.black,
+                    Color::White => &theme.white,
+                };
+                let _ = writeln!(
+                    svg,
+                    r#"<circle cx="{x}" cy="{y}" r="{}" fill="{fill}" stroke="{}"/>"#,
+                    cell * 0.47,
+                    theme.stones_border
+                );
+            }
+        }
+
+        if let Some(dead_stones) = &self.territory {
+            let side = cell * 0.4;
+            for (index, ownership) in goban.ownership_map(dead_stones).into_iter().enumerate() {
+                let Ownership::Territory(color) = ownership else {
+                    continue;
+                };
+                let (x, y) = self.position(one_to_2dim(goban.size(), index));
+                let fill = match color {
+                    Color::Black => &theme.black,
+                    Color::White => &theme.white,
+                };
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{side}" height="{side}" fill="{fill}" stroke="{}"/>"#,
+                    x - side / 2.,
+                    y - side / 2.,
+                    theme.stones_border
+                );
+            }
+        }
+
+        let numbered = |coord: Coord| {
+            numbers
+                .as_ref()
+                .and_then(|numbers| numbers[two_to_1dim(goban.size(), coord)])
+        };
+        if let Some(numbers) = &numbers {
+            for (index, number) in numbers.iter().enumerate() {
+                let Some(number) = number else {
+                    continue;
+                };
+                let coord = one_to_2dim(goban.size(), index);
+                let (x, y) = self.position(coord);
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{x}" y="{y}" font-family="sans-serif" font-size="{font_size}" text-anchor="middle" dominant-baseline="central" fill="{}">{number}</text>"#,
+                    theme.mark_color(goban, coord)
+                );
+            }
+        }
+
+        // The last move is marked only if it's not numbered.
+        if let Some(coord) = last_move.filter(|&coord| numbered(coord).is_none()) {
+            let (x, y) = self.position(coord);
+            let _ = writeln!(
+                svg,
+                r#"<circle cx="{x}" cy="{y}" r="{}" fill="none" stroke="{}" stroke-width="2"/>"#,
+                cell * 0.22,
+                theme.mark_color(goban, coord)
+            );
+        }
+
+        if let Some(coord) = ko {
+            let (x, y) = self.position(coord);
+            let side = cell * 0.5;
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{side}" height="{side}" fill="none" stroke="{}" stroke-width="2"/>"#,
+                x - side / 2.,
+                y - side / 2.,
+                theme.lines
+            );
+        }
+
+        for (coord, markup) in self
+            .markup
+            .iter()
+            .filter(|(coord, _)| is_coord_valid(goban.size(), *coord))
+        {
+            svg.push_str(&self.render_markup(theme, goban, *coord, markup));
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn render_markup(&self, theme: &Theme, goban: &Goban, coord: Coord, markup: &Markup) -> String {
+        let (x, y) = self.position(coord);
+        let cell = self.cell_size;
+        let color = theme.mark_color(goban, coord);
+        let r = cell * 0.25;
+        match markup {
+            Markup::Triangle => format!(
+                "<polygon points=\"{x},{} {},{} {},{}\" fill=\"none\" stroke=\"{color}\" stroke-width=\"2\"/>\n",
+                y - r,
+                x - r * 0.87,
+                y + r * 0.5,
+                x + r * 0.87,
+                y + r * 0.5
+            ),
+            Markup::Square => format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"{color}\" stroke-width=\"2\"/>\n",
+                x - r * 0.8,
+                y - r * 0.8,
+                r * 1.6,
+                r * 1.6
+            ),
+            Markup::Circle => format!(
+                "<circle cx=\"{x}\" cy=\"{y}\" r=\"{r}\" fill=\"none\" stroke=\"{color}\" stroke-width=\"2\"/>\n"
+            ),
+            Markup::Cross => format!(
+                "<path d=\"M{} {}L{} {}M{} {}L{} {}\" stroke=\"{color}\" stroke-width=\"2\"/>\n",
+                x - r,
+                y - r,
+                x + r,
+                y + r,
+                x - r,
+                y + r,
+                x + r,
+                y - r
+            ),
+            Markup::Label(label) => {
+                let mut res = String::new();
+                if goban.get_color(coord).is_none() {
+                    // Hides the lines under the label.
+                    let _ = writeln!(
+                        res,
+                        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                        x - cell * 0.35,
+                        y - cell * 0.35,
+                        cell * 0.7,
+                        cell * 0.7,
+                        theme.background
+                    );
+                }
+                let _ = writeln!(
+                    res,
+                    r#"<text x="{x}" y="{y}" font-family="sans-serif" font-size="{}" text-anchor="middle" dominant-baseline="central" fill="{color}">{}</text>"#,
+                    cell * 0.45,
+                    escape_xml(label)
+                );
+                res
+            }
+        }
+    }
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,291 @@
+//! Module for printing a goban or a game on a terminal, or in the logs.
+//! # Example
+//! ```
+//! use goban::render::terminal::TerminalRenderer;
+//! use goban::rules::game::Game;
+//! use goban::rules::{GobanSizes, Move, CHINESE};
+//!
+//! let mut game = Game::new(GobanSizes::Custom(5, 5), CHINESE);
+//! game.play(Move::Play(1, 1)).play(Move::Play(3, 3));
+//! let text = TerminalRenderer::new().ascii(true).render_game(&game);
+//! assert_eq!(
+//!     text,
+//!     "   A B C D E
+//!  5 . . . . . 5
+//!  4 . X . . . 4
+//!  3 . . . . . 3
+//!  2 . . .(O). 2
+//!  1 . . . . . 1
+//!    A B C D E
+//! "
+//! );
+//! ```
+
+use std::fmt::Write;
+
+use crate::notation::gtp_column;
+use crate::pieces::goban::Goban;
+use crate::pieces::stones::Color;
+use crate::pieces::territory::Ownership;
+use crate::pieces::util::coord::{two_to_1dim, Coord};
+use crate::pieces::Nat;
+use crate::render::{last_stone, star_points};
+use crate::rules::game::Game;
+
+/// The side of the goban at the bottom of the drawing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// The usual view, the A1 point is in the bottom left corner.
+    Black,
+    /// The view of the White player, the goban is turned upside down.
+    White,
+}
+
+const RESET: &str = "\x1b[0m";
+const BOARD: &str = "\x1b[30;43m";
+const BLACK_STONE: &str = "\x1b[1;30;43m";
+const WHITE_STONE: &str = "\x1b[1;97;43m";
+const BLACK_TERRITORY: &str = "\x1b[30;100m";
+const WHITE_TERRITORY: &str = "\x1b[30;47m";
+
+/// Options of the terminal rendering.
+#[derive(Debug, Clone)]
+pub struct TerminalRenderer {
+    coordinates: bool,
+    orientation: Orientation,
+    ansi: bool,
+    last_move: bool,
+    star_points: bool,
+    ascii: bool,
+    territory: Option<Vec<Coord>>,
+}
+
+impl TerminalRenderer {
+    /// Creates a renderer with the coordinates, the last move and the star points, without
+    /// colors.
+    pub fn new() -> Self {
+        TerminalRenderer {
+            coordinates: true,
+            orientation: Orientation::Black,
+            ansi: false,
+            last_move: true,
+            star_points: true,
+            ascii: false,
+            territory: None,
+        }
+    }
+
+    /// Prints the GTP coordinates around the goban.
+    pub fn coordinates(&mut self, coordinates: bool) -> &mut Self {
+        self.coordinates = coordinates;
+        self
+    }
+
+    /// Side of the goban at the bottom, Black by default.
+    pub fn orientation(&mut self, orientation: Orientation) -> &mut Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Colors the goban with the ANSI escape codes.
+    pub fn ansi(&mut self, ansi: bool) -> &mut Self {
+        self.ansi = ansi;
+        self
+    }
+
+    /// Marks the last stone played, only for the games.
+    pub fn last_move(&mut self, last_move: bool) -> &mut Self {
+        self.last_move = last_move;
+        self
+    }
+
+    /// Marks the star points given by `render::star_points`, on the gobans with both sides of
+    /// 7 or more, square or not.
+    pub fn star_points(&mut self, star_points: bool) -> &mut Self {
+        self.star_points = star_points;
+        self
+    }
+
+    /// Uses only ASCII characters: `X` and `O` for the stones, `.` and `+` for the
+    /// intersections, the last move is between parentheses.
+    pub fn ascii(&mut self, ascii: bool) -> &mut Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Shows the territories, the chains with a stone in `dead_stones` are dead.
+    /// Without colors the territories are `b` and `w` in ASCII, `▪` and `▫` else.
+    pub fn territory(&mut self, dead_stones: &[Coord]) -> &mut Self {
+        self.territory = Some(dead_stones.to_vec());
+        self
+    }
+
+    /// Renders the goban alone.
+    pub fn render_goban(&self, goban: &Goban) -> String {
+        self.render(goban, None)
+    }
+
+    /// Renders the goban of the game with the last move.
+    pub fn render_game(&self, game: &Game) -> String {
+        self.render(game.goban(), last_stone(game).filter(|_| self.last_move))
+    }
+
+    /// Returns the character of the intersection and its ANSI style.
+    fn intersection(
+        &self,
+        goban: &Goban,
+        coord: Coord,
+        (top, bottom, left, right): (bool, bool, bool, bool),
+        is_last_move: bool,
+        is_star_point: bool,
+        ownership: Option<Ownership>,
+    ) -> (char, &'static str) {
+        match (goban.get_color(coord), ownership) {
+            (Some(Color::Black), Some(Ownership::Territory(Color::White))) => {
+                (if self.ascii { 'X' } else { '●' }, WHITE_TERRITORY)
+            }
+            (Some(Color::White), Some(Ownership::Territory(Color::Black))) => {
+                (if self.ascii { 'O' } else { '○' }, BLACK_TERRITORY)
+            }
+            (Some(Color::Black), _) => match (self.ascii, is_last_move) {
+                (true, _) => ('X', BLACK_STONE),
+                (false, true) => ('◉', BLACK_STONE),
+                (false, false) => ('●', BLACK_STONE),
+            },
+            (Some(Color::White), _) => match (self.ascii, is_last_move) {
+                (true, _) => ('O', WHITE_STONE),
+                (false, true) => ('◎', WHITE_STONE),
+                (false, false) => ('○', WHITE_STONE),
+            },
+            (None, Some(Ownership::Territory(color))) => {
+                let style = match color {
+                    Color::Black => BLACK_TERRITORY,
+                    Color::White => WHITE_TERRITORY,
+                };
+                let c = match (self.ansi, self.ascii, color) {
+                    (true, true, _) => '.',
+                    (true, false, _) => '┼',
+                    (false, true, Color::Black) => 'b',
+                    (false, true, Color::White) => 'w',
+                    (false, false, Color::Black) => '▪',
+                    (false, false, Color::White) => '▫',
+                };
+                (c, style)
+            }
+            (None, _) if is_star_point => (if self.ascii { '+' } else { '╋' }, BOARD),
+            (None, _) if self.ascii => ('.', BOARD),
+            (None, _) => {
+                let c = match (top, bottom, left, right) {
+                    (true, _, true, _) => '┏',
+                    (true, _, _, true) => '┓',
+                    (_, true, true, _) => '┗',
+                    (_, true, _, true) => '┛',
+                    (true, _, _, _) => '┯',
+                    (_, true, _, _) => '┷',
+                    (_, _, true, _) => '┠',
+                    (_, _, _, true) => '┨',
+                    _ => '┼',
+                };
+                (c, BOARD)
+            }
+        }
+    }
+
+    fn render(&self, goban: &Goban, last_move: Option<Coord>) -> String {
+        let (height, width) = goban.size();
+        let ownership = self
+            .territory
+            .as_ref()
+            .map(|dead_stones| goban.ownership_map(dead_stones));
+        let (lines, columns): (Vec<Nat>, Vec<Nat>) = match self.orientation {
+            Orientation::Black => ((0..height).collect(), (0..width).collect()),
+            Orientation::White => ((0..height).rev().collect(), (0..width).rev().collect()),
+        };
+        let stars = if self.star_points {
+            star_points(goban.size())
+        } else {
+            vec![]
+        };
+        let mut buff = String::new();
+
+        let labels = {
+            // In ASCII the intersections are preceded by a space.
+            let (separator, mut labels) = if self.ascii {
+                (" ", String::from("  "))
+            } else {
+                ("", String::from("   "))
+            };
+            for &column in &columns {
+                labels.push_str(separator);
+                labels.push(gtp_column(column));
+            }
+            labels.push('\n');
+            labels
+        };
+        if self.coordinates {
+            buff.push_str(&labels);
+        }
+        for (row, &line) in lines.iter().enumerate() {
+            if self.coordinates {
+                let _ = write!(buff, "{:>2}", height - line);
+                if !self.ascii {
+                    buff.push(' ');
+                }
+            }
+            // In ASCII the character before an intersection is a space, or the parenthesis
+            // around the last move.
+            let mut before = ' ';
+            for (index, &column) in columns.iter().enumerate() {
+                let coord = (line, column);
+                let is_last_move = last_move == Some(coord);
+                let edges = (
+                    row == 0,
+                    row + 1 == lines.len(),
+                    index == 0,
+                    index + 1 == columns.len(),
+                );
+                let (c, style) = self.intersection(
+                    goban,
+                    coord,
+                    edges,
+                    is_last_move,
+                    stars.contains(&coord),
+                    ownership
+                        .as_ref()
+                        .map(|ownership| ownership[two_to_1dim(goban.size(), coord)]),
+                );
+                if self.ansi {
+                    buff.push_str(style);
+                }
+                if self.ascii {
+                    buff.push(if is_last_move { '(' } else { before });
+                    before = if is_last_move { ')' } else { ' ' };
+                }
+                buff.push(c);
+            }
+            if self.ascii {
+                buff.push(before);
+            }
+            if self.ansi {
+                buff.push_str(RESET);
+            }
+            if self.coordinates {
+                if !self.ascii {
+                    buff.push(' ');
+                }
+                let _ = write!(buff, "{}", height - line);
+            }
+            buff.push('\n');
+        }
+        if self.coordinates {
+            buff.push_str(&labels);
+        }
+        buff
+    }
+}
+
+impl Default for TerminalRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,394 @@
+//! Module for the game clocks and the time controls.
+//! The clock doesn't measure time itself, the elapsed time of each move is supplied by the
+//! caller.
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use goban::rules::clock::{Clock, TimeControl};
+//! use goban::rules::game::Game;
+//! use goban::rules::{EndGame, Move};
+//! use goban::pieces::stones::Color;
+//!
+//! let mut game = Game::builder()
+//!     .size((9, 9))
+//!     .time_control(TimeControl::Absolute {
+//!         main_time: Duration::from_secs(60),
+//!     })
+//!     .build()
+//!     .unwrap();
+//! game.play_with_time(Move::Play(4, 4), Duration::from_secs(10));
+//! game.play_with_time(Move::Play(2, 2), Duration::from_secs(61));
+//! assert_eq!(game.outcome(), Some(EndGame::WinnerByTime(Color::Black)));
+//! ```
+
+use std::time::Duration;
+
+use crate::pieces::stones::Color;
+
+/// The time controls supported by the clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeControl {
+    /// Each player has `main_time` for the whole game.
+    Absolute { main_time: Duration },
+    /// Each move adds `increment` to the time of the player.
+    Fischer {
+        main_time: Duration,
+        increment: Duration,
+    },
+    /// After the main time, each move must be played in `period_time`, every period exceeded
+    /// is lost. The player loses when all the periods are lost.
+    ByoYomi {
+        main_time: Duration,
+        period_time: Duration,
+        periods: u32,
+    },
+    /// After the main time, `stones` moves must be played in `period_time`.
+    Canadian {
+        main_time: Duration,
+        period_time: Duration,
+        stones: u32,
+    },
+}
+
+impl TimeControl {
+    pub fn main_time(&self) -> Duration {
+        match *self {
+            TimeControl::Absolute { main_time }
+            | TimeControl::Fischer { main_time, .. }
+            | TimeControl::ByoYomi { main_time, .. }
+            | TimeControl::Canadian { main_time, .. } => main_time,
+        }
+    }
+
+    /// Creates a time control from the SGF properties TM (main time in seconds) and OT
+    /// (overtime description). The overtimes understood are "5x30 byo-yomi", "25/600 Canadian"
+    /// and "10 fischer".
+    pub fn from_sgf(main_time: u32, overtime: Option<&str>) -> Result<Self, String> {
+        let main_time = Duration::from_secs(main_time as u64);
+        let overtime = match overtime.map(str::trim) {
+            None | Some("") => return Ok(TimeControl::Absolute { main_time }),
+            Some(overtime) => overtime,
+        };
+        let err = || format!("The overtime {overtime} is not supported");
+        let (value, kind) = overtime.split_once(char::is_whitespace).ok_or_else(err)?;
+        let parse = |x: &str| x.trim().parse::<u32>().map_err(|_| err());
+        match kind.trim().to_lowercase().as_str() {
+            "byo-yomi" | "byoyomi" => {
+                let (periods, period_time) = value.split_once('x').ok_or_else(err)?;
+                Ok(TimeControl::ByoYomi {
+                    main_time,
+                    period_time: Duration::from_secs(parse(period_time)? as u64),
+                    periods: parse(periods)?,
+                })
+            }
+            "canadian" => {
+                let (stones, period_time) = value.split_once('/').ok_or_else(err)?;
+                Ok(TimeControl::Canadian {
+                    main_time,
+                    period_time: Duration::from_secs(parse(period_time)? as u64),
+                    stones: parse(stones)?,
+                })
+            }
+            "fischer" => Ok(TimeControl::Fischer {
+                main_time,
+                increment: Duration::from_secs(parse(value)? as u64),
+            }),
+            _ => Err(err()),
+        }
+    }
+
+    /// Returns the SGF properties TM and OT of the time control. Ex: `TM[600]OT[5x30 byo-yomi]`
+    pub fn to_sgf(&self) -> String {
+        let overtime = match *self {
+            TimeControl::Absolute { .. } => String::new(),
+            TimeControl::Fischer { increment, .. } => {
+                format!("OT[{} fischer]", increment.as_secs())
+            }
+            TimeControl::ByoYomi {
+                period_time,
+                periods,
+                ..
+            } => format!("OT[{}x{} byo-yomi]", periods, period_time.as_secs()),
+            TimeControl::Canadian {
+                period_time,
+                stones,
+                ..
+            } => format!("OT[{}/{} Canadian]", stones, period_time.as_secs()),
+        };
+        format!("TM[{}]{}", self.main_time().as_secs(), overtime)
+    }
+
+    /// Creates a time control from the arguments of the GTP command
+    /// `time_settings main_time byo_yomi_time byo_yomi_stones`.
+    /// Returns None if the arguments means no time limits.
+    pub fn from_gtp(args: &str) -> Result<Option<Self>, String> {
+        let values = args
+            .split_whitespace()
+            .map(|x| x.parse::<u32>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Invalid time_settings arguments {args}: {e}"))?;
+        let [main_time, period_time, stones] = values[..] else {
+            return Err(format!("time_settings needs 3 arguments, got: {args}"));
+        };
+        let main_time = Duration::from_secs(main_time as u64);
+        let period_time = Duration::from_secs(period_time as u64);
+        Ok(match (period_time.is_zero(), stones) {
+            (true, _) => Some(TimeControl::Absolute { main_time }),
+            (false, 0) => None,
+            (false, stones) => Some(TimeControl::Canadian {
+                main_time,
+                period_time,
+                stones,
+            }),
+        })
+    }
+
+    /// Returns the arguments of the GTP command `time_settings`. GTP only knows canadian
+    /// overtime, so byo-yomi is sent as one stone per period and Fischer as absolute time.
+    pub fn to_gtp(&self) -> String {
+        match *self {
+            TimeControl::Absolute { main_time } | TimeControl::Fischer { main_time, .. } => {
+                format!("{} 0 0", main_time.as_secs())
+            }
+            TimeControl::ByoYomi {
+                main_time,
+                period_time,
+                ..
+            } => format!("{} {} 1", main_time.as_secs(), period_time.as_secs()),
+            TimeControl::Canadian {
+                main_time,
+                period_time,
+                stones,
+            } => format!(
+                "{} {} {}",
+                main_time.as_secs(),
+                period_time.as_secs(),
+                stones
+            ),
+        }
+    }
+}
+
+/// Remaining time of a player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CopyGetters)]
+pub struct PlayerClock {
+    /// Remaining main time.
+    #[get_copy = "pub"]
+    main_time: Duration,
+    /// Remaining time in the current canadian period.
+    #[get_copy = "pub"]
+    period_time: Duration,
+    /// Remaining byo-yomi periods, or remaining stones in the current canadian period.
+    #[get_copy = "pub"]
+    periods: u32,
+    /// True if the player has run out of time.
+    #[get_copy = "pub"]
+    flagged: bool,
+}
+
+impl PlayerClock {
+    fn new(time_control: TimeControl) -> Self {
+        let (period_time, periods) = match time_control {
+            TimeControl::Absolute { .. } | TimeControl::Fischer { .. } => (Duration::ZERO, 0),
+            TimeControl::ByoYomi {
+                period_time,
+                periods,
+                ..
+            } => (period_time, periods),
+            TimeControl::Canadian {
+                period_time,
+                stones,
+                ..
+            } => (period_time, stones),
+        };
+        PlayerClock {
+            main_time: time_control.main_time(),
+            period_time,
+            periods,
+            flagged: false,
+        }
+    }
+
+    /// True if the main time is over.
+    #[inline]
+    pub fn in_overtime(&self) -> bool {
+        self.main_time.is_zero()
+    }
+}
+
+/// Clock of a game, it keeps the remaining time of both players.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CopyGetters)]
+pub struct Clock {
+    #[get_copy = "pub"]
+    time_control: TimeControl,
+    black: PlayerClock,
+    white: PlayerClock,
+}
+
+impl Clock {
+    pub fn new(time_control: TimeControl) -> Self {
+        Clock {
+            time_control,
+            black: PlayerClock::new(time_control),
+            white: PlayerClock::new(time_control),
+        }
+    }
+
+    /// Returns the remaining time of the player.
+    pub fn player(&self, color: Color) -> &PlayerClock {
+        match color {
+            Color::Black => &self.black,
+            Color::White => &self.white,
+        }
+    }
+
+    fn player_mut(&mut self, color: Color) -> &mut PlayerClock {
+        match color {
+            Color::Black => &mut self.black,
+            Color::White => &mut self.white,
+        }
+    }
+
+    /// Deducts the time spent by the player on a move.
+    /// Returns false if the player has run out of time.
+    pub fn spend(&mut self, color: Color, elapsed: Duration) -> bool {
+        let time_control = self.time_control;
+        let player = self.player_mut(color);
+        if player.flagged {
+            return false;
+        }
+        let overtime = elapsed.saturating_sub(player.main_time);
+        player.main_time = player.main_time.saturating_sub(elapsed);
+        match time_control {
+            TimeControl::Absolute { .. } => player.flagged = !overtime.is_zero(),
+            TimeControl::Fischer { increment, .. } => {
+                player.flagged = !overtime.is_zero();
+                if !player.flagged {
+                    player.main_time += increment;
+                }
+            }
+            TimeControl::ByoYomi { period_time, .. } => {
+                let mut overtime = overtime;
+                while overtime > period_time && !player.flagged {
+                    overtime -= period_time;
+                    player.periods = player.periods.saturating_sub(1);
+                    player.flagged = player.periods == 0;
+                }
+            }
+            TimeControl::Canadian {
+                period_time,
+                stones,
+                ..
+            } => {
+                if player.in_overtime() {
+                    if overtime > player.period_time {
+                        player.flagged = true;
+                    } else {
+                        player.period_time -= overtime;
+                        player.periods = player.periods.saturating_sub(1);
+                        if player.periods == 0 {
+                            player.period_time = period_time;
+                            player.periods = stones;
+                        }
+                    }
+                }
+            }
+        }
+        !player.flagged
+    }
+
+    /// Sets the remaining time of a player, like the GTP command `time_left` or the SGF
+    /// properties BL/WL and OB/OW.
+    /// In overtime `time_left` is the time of the current period and `periods` the number of
+    /// byo-yomi periods, or canadian stones, left. `periods` is ignored in main time.
+    pub fn set_time_left(&mut self, color: Color, time_left: Duration, periods: u32) {
+        let time_control = self.time_control;
+        let player = self.player_mut(color);
+        let overtime = match time_control {
+            TimeControl::ByoYomi { .. } | TimeControl::Canadian { .. } => periods != 0,
+            _ => false,
+        };
+        player.flagged = false;
+        if overtime {
+            player.main_time = Duration::ZERO;
+            player.period_time = time_left;
+            player.periods = periods;
+        } else {
+            player.main_time = time_left;
+        }
+    }
+
+    /// Returns the arguments of the GTP command `time_left` for the player.
+    /// Ex: "black 300 0"
+    pub fn to_gtp_time_left(&self, color: Color) -> String {
+        let player = self.player(color);
+        let color = match color {
+            Color::Black => "black",
+            Color::White => "white",
+        };
+        match self.time_control {
+            TimeControl::ByoYomi { period_time, .. } if player.in_overtime() => {
+                format!("{} {} 1", color, period_time.as_secs())
+            }
+            TimeControl::Canadian { .. } if player.in_overtime() => format!(
+                "{} {} {}",
+                color,
+                player.period_time.as_secs(),
+                player.periods
+            ),
+            _ => format!("{} {} 0", color, player.main_time.as_secs()),
+        }
+    }
+
+    /// Applies the arguments of the GTP command `time_left color time stones`.
+    pub fn apply_gtp_time_left(&mut self, args: &str) -> Result<(), String> {
+        let err = || format!("Invalid time_left arguments: {args}");
+        let mut args_it = args.split_whitespace();
+        let color = match args_it.next().map(str::to_lowercase).as_deref() {
+            Some("b" | "black") => Color::Black,
+            Some("w" | "white") => Color::White,
+            _ => return Err(err()),
+        };
+        let mut next_number = || {
+            args_it
+                .next()
+                .and_then(|x| x.parse::<u32>().ok())
+                .ok_or_else(err)
+        };
+        let time_left = Duration::from_secs(next_number()? as u64);
+        let stones = next_number()?;
+        let periods = match self.time_control {
+            // GTP sends byo-yomi as one stone per period, the number of periods is unknown.
+            TimeControl::ByoYomi { .. } if stones != 0 => self.player(color).periods.max(1),
+            _ => stones,
+        };
+        self.set_time_left(color, time_left, periods);
+        Ok(())
+    }
+
+    /// Returns the SGF properties of the remaining time of the player. Ex: `BL[245]OB[3]`
+    pub fn to_sgf_time_left(&self, color: Color) -> String {
+        let player = self.player(color);
+        let (time, periods) = match color {
+            Color::Black => ("BL", "OB"),
+            Color::White => ("WL", "OW"),
+        };
+        match self.time_control {
+            TimeControl::ByoYomi { period_time, .. } if player.in_overtime() => format!(
+                "{}[{}]{}[{}]",
+                time,
+                period_time.as_secs(),
+                periods,
+                player.periods
+            ),
+            TimeControl::Canadian { .. } if player.in_overtime() => format!(
+                "{}[{}]{}[{}]",
+                time,
+                player.period_time.as_secs(),
+                periods,
+                player.periods
+            ),
+            _ => format!("{}[{}]", time, player.main_time.as_secs()),
+        }
+    }
+}
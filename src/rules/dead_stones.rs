@@ -1,218 +1,89 @@
-use std::collections::{HashMap, HashSet};
+//! Experimental dead stones detection with Monte-Carlo playouts.
 
-use oxymcts::{
-    DefaultBackProp, DefaultLazyTreePolicy, Evaluator, GameTrait, LazyMcts, LazyMctsNode,
-    Num, Playout, uct_value,
-};
-use rand::prelude::{SliceRandom, ThreadRng};
-use rand::thread_rng;
+use std::collections::{HashMap, HashSet};
 
-use ahash::AHashSet;
+use rand::Rng;
 
-use crate::pieces::goban::Goban;
-use crate::pieces::GoStringPtr;
-use crate::pieces::stones::{Color, Point};
-use crate::rules::{Color, IllegalRules, Move};
+use crate::pieces::board::Board;
+use crate::pieces::stones::{Color, Stone};
+use crate::pieces::util::coord::{one_to_2dim, valid_coords, Coord};
 use crate::rules::game::Game;
 
-impl GameTrait for Game {
-    type Player = Color;
-    type Move = Move;
-
-    fn legals_moves(&self) -> Vec<Self::Move> {
-        let moves = self
-            .legals_by(self.rule.illegal_flag() | IllegalRules::FILLEYE)
-            .map(Move::from)
-            .collect::<Vec<_>>();
-        if moves.is_empty() {
-            if self.is_over() {
-                vec![]
-            } else {
-                vec![Move::Pass]
-            }
-        } else {
-            moves
-        }
-    }
-
-    fn player_turn(&self) -> Self::Player {
-        self.turn
-    }
-
-    fn hash(&self) -> u64 {
-        0
-    }
-
-    fn is_final(&self) -> bool {
-        self.is_over()
-    }
-
-    fn do_move(&mut self, m: &Self::Move) {
-        self.play(*m);
-    }
-
-    fn get_winner(&self) -> Self::Player {
-        self.outcome()
-            .unwrap()
-            .get_winner()
-            .expect("The game was ex aequo")
-    }
-}
-
-struct Eval;
-
-#[derive(Clone)]
-struct EvalR {
-    reward: u32,
-    last_board: Goban,
-}
-
-type Reward = u64;
-
-impl Evaluator<Game, Reward, ()> for Eval {
-    type Args = f64;
-    type EvalResult = Reward;
-
-    fn eval_child(
-        child: &LazyMctsNode<Game, Reward, ()>,
-        _turn: &<Game as GameTrait>::Player,
-        parent_visits: u32,
-        args: &Self::Args,
-    ) -> Num {
-        uct_value(
-            parent_visits,
-            child.sum_rewards as f64,
-            child.n_visits,
-            *args,
-        )
-    }
-
-    fn evaluate_leaf(child: Game, turn: &<Game as GameTrait>::Player) -> Self::EvalResult {
-        let winner = child.get_winner();
-        if winner == *turn {
-            1
-        } else {
-            0
-        }
-    }
-}
-
-struct PL;
-
-impl Playout<Game> for PL {
-    type Args = ();
-
-    fn playout(mut state: Game, _args: ()) -> Game {
-        fn fast_play_random(state: &Game, thread_rng: &mut ThreadRng) -> Move {
-            let mut v: Vec<_> = state.pseudo_legals().collect();
-            v.shuffle(thread_rng);
-            for coordinates in v
-                .into_iter()
-                .filter(|&point| state.check_point(point).is_none())
-            {
-                if !state.check_eye(Point {
-                    coord: coordinates,
-                    color: state.turn().stone_color(),
+impl<B: Board> Game<B> {
+    /// Returns the chains that don't have at least two eyes, they are the only ones that can
+    /// be dead. Each chain is returned as the coordinates of its stones.
+    fn get_floating_chains(&self) -> Vec<Vec<Coord>> {
+        let size = self.size();
+        let mut eyes_by_chain: HashMap<usize, u32> = HashMap::new();
+        for point in self.pseudo_legals() {
+            for color in [Color::Black, Color::White] {
+                if self.check_eye(Stone {
+                    coord: point,
+                    color,
                 }) {
-                    return coordinates.into();
+                    let chains_around_eye = valid_coords(point, size)
+                        .into_iter()
+                        .filter_map(|neighbor| self.goban.chain_id(neighbor))
+                        .collect::<HashSet<_>>();
+                    for chain_id in chains_around_eye {
+                        *eyes_by_chain.entry(chain_id).or_insert(0) += 1;
+                    }
                 }
             }
-            Move::Pass
         }
-        let mut thread_rng = thread_rng();
-        while !state.is_over() {
-            state.play(fast_play_random(&state, &mut thread_rng));
-        }
-        state
-    }
-}
-
-type Mcts<'a> = LazyMcts<
-    'a,
-    Game,
-    DefaultLazyTreePolicy<Game, Eval, (), u64>,
-    PL,
-    DefaultBackProp,
-    Eval,
-    (),
-    u64,
->;
 
-impl Game {
-    fn get_floating_stones(&self) -> Vec<GoStringPtr> {
-        let eyes = self.pseudo_legals().filter(|&p| {
-            self.check_eye(Point {
-                coord: p,
-                color: Color::Black,
-            }) || self.check_eye(Point {
-                coord: p,
-                color: Color::White,
-            })
-        });
-        let mut strings_wth_eye = HashMap::new();
-        for eye in eyes {
-            let string_connected_eye = self
-                .goban
-                .get_neighbors_chains_ids_by_board_idx(eye)
-                .collect::<HashSet<_>>();
-            for x in string_connected_eye {
-                strings_wth_eye
-                    .entry(x)
-                    .and_modify(|v| *v += 1)
-                    .or_insert(0);
-            }
-        }
-        let all_strings = self
-            .goban
-            .go_strings()
-            .iter()
-            .cloned()
-            .filter_map(|x| x)
-            .collect::<HashSet<_>>();
-        let string_with_2eyes = strings_wth_eye
+        let mut chains = (0..size.0 as usize * size.1 as usize)
+            .map(|index| one_to_2dim(size, index))
+            .filter_map(|point| self.goban.chain_id(point).map(|chain_id| (chain_id, point)))
+            .filter(|(chain_id, _)| eyes_by_chain.get(chain_id).is_none_or(|&eyes| eyes < 2))
+            .collect::<Vec<_>>();
+        chains.sort_by_key(|&(chain_id, _)| chain_id);
+        chains.dedup_by_key(|&mut (chain_id, _)| chain_id);
+        chains
             .into_iter()
-            .filter(|(_, v)| *v >= 2)
-            .map(|x| x.0)
-            .collect::<HashSet<_>>();
-
-        all_strings
-            .difference(&string_with_2eyes)
-            .cloned()
+            .map(|(_, point)| self.goban.chain_stones(point))
             .collect()
     }
 
-    pub fn dead_stones_wth_simulations(&self, nb_simulations: usize) -> AHashSet<GoStringPtr> {
-        let mut game = self.branch();
-        let floating_stones = self.get_floating_stones();
-        while !game.is_over() {
-            let m = {
-                let mut mcts = Mcts::with_capacity(&game, nb_simulations);
-                for _ in 0..nb_simulations {
-                    mcts.execute(&2.0_f64.sqrt(), ());
-                }
-                mcts.best_move(&2.0_f64.sqrt())
-            };
-            game.play(m);
-        }
-        let final_state_raw = game.goban().raw();
-        let mut dead_ren = AHashSet::new();
-        for ren_ptr in floating_stones {
-            for &stone in ren_ptr.stones() {
-                // If some stones of the string arent in the final goban then it's plausible that
-                // this string is dead.
-                if final_state_raw[stone] != ren_ptr.color() {
-                    dead_ren.insert(ren_ptr);
-                    break;
+    /// Returns the chains that are potentially dead. A chain is considered dead if it doesn't
+    /// have two eyes and it's captured in at least half of the `nb_simulations` random playouts.
+    /// Each dead chain is returned as the coordinates of its stones.
+    /// The playouts use the generator passed in parameter, so a seeded generator gives
+    /// reproducible results.
+    pub fn dead_stones_wth_simulations(
+        &self,
+        nb_simulations: usize,
+        rng: &mut impl Rng,
+    ) -> Vec<Vec<Coord>> {
+        let floating_chains = self.get_floating_chains();
+        let mut captures = vec![0; floating_chains.len()];
+        for _ in 0..nb_simulations {
+            let final_state = self.playout(rng);
+            for (stones, captured) in floating_chains.iter().zip(captures.iter_mut()) {
+                let color = self.goban.get_color(stones[0]);
+                // If some stones of the chain aren't in the final goban then it's plausible that
+                // this chain is dead.
+                if stones
+                    .iter()
+                    .any(|&stone| final_state.goban.get_color(stone) != color)
+                {
+                    *captured += 1;
                 }
             }
         }
-        dead_ren
+
+        floating_chains
+            .into_iter()
+            .zip(captures)
+            .filter(|&(_, captured)| nb_simulations != 0 && 2 * captured >= nb_simulations)
+            .map(|(stones, _)| stones)
+            .collect()
     }
 
-    /// Return an array of dead stones, works better if the game if ended.
+    /// Return the dead chains, works better if the game if ended.
     /// the "dead" stones are only potentially dead.
     #[inline]
-    pub fn dead_stones(&self) -> AHashSet<GoStringPtr> {
-        self.dead_stones_wth_simulations(600)
+    pub fn dead_stones(&self, rng: &mut impl Rng) -> Vec<Vec<Coord>> {
+        self.dead_stones_wth_simulations(600, rng)
     }
 }
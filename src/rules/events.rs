@@ -0,0 +1,99 @@
+//! Module for listening the changes of a game, for animating the captures in a UI or sending
+//! them on the network without comparing the gobans after every move.
+//! # Example
+//! ```
+//! use std::sync::{Arc, Mutex};
+//!
+//! use goban::rules::events::GameEvent;
+//! use goban::rules::game::Game;
+//! use goban::rules::Move;
+//!
+//! let mut game = Game::default();
+//! let events = Arc::new(Mutex::new(vec![]));
+//! let sink = events.clone();
+//! game.subscribe(move |event| sink.lock().unwrap().push(event.clone()));
+//! game.play(Move::Pass).play(Move::Pass);
+//! assert_eq!(events.lock().unwrap().last(), Some(&GameEvent::ScoringPhase));
+//! ```
+
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use crate::pieces::stones::{Color, Stone};
+use crate::pieces::util::coord::Coord;
+use crate::rules::EndGame;
+
+/// The changes of a game, sent to the listeners after they happened.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    /// A stone is put on the goban.
+    StonePlaced(Stone),
+    /// Chains are removed from the goban, with the color of the captured stones.
+    ChainsCaptured {
+        color: Color,
+        chains: Vec<Vec<Coord>>,
+    },
+    /// The point the next player can't retake.
+    KoEstablished(Coord),
+    /// The player passed.
+    Pass(Color),
+    /// A player resigned, the color is the winner like in `Move::Resign`.
+    Resign(Color),
+    /// The game is finished by resignation or time.
+    GameOver(EndGame),
+    /// Two passes in a row, the dead stones can be marked and the game scored.
+    ScoringPhase,
+    /// The game is resumed after the two passes.
+    Resumed,
+}
+
+/// Identifier of a listener, to unsubscribe it.
+pub type ListenerId = usize;
+
+type Listener = Arc<dyn Fn(&GameEvent) + Send + Sync>;
+
+/// The listeners of a game.
+/// The listeners aren't cloned with the game, because the clones are used for simulations.
+#[derive(Default)]
+pub struct Listeners {
+    next_id: ListenerId,
+    listeners: Vec<(ListenerId, Listener)>,
+}
+
+impl Listeners {
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.listeners.is_empty()
+    }
+
+    pub(super) fn add(&mut self, listener: Listener) -> ListenerId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.listeners.push((id, listener));
+        id
+    }
+
+    pub(super) fn remove(&mut self, id: ListenerId) -> bool {
+        let len = self.listeners.len();
+        self.listeners.retain(|(listener_id, _)| *listener_id != id);
+        len != self.listeners.len()
+    }
+
+    pub(super) fn notify(&self, event: GameEvent) {
+        for (_, listener) in &self.listeners {
+            listener(&event);
+        }
+    }
+}
+
+impl Clone for Listeners {
+    fn clone(&self) -> Self {
+        Listeners::default()
+    }
+}
+
+impl Debug for Listeners {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Listeners({})", self.listeners.len())
+    }
+}
@@ -1,21 +1,35 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use hash_hasher::{HashBuildHasher, HashedSet};
 
+use crate::pieces::board::Board;
+use crate::pieces::diagram::Diagram;
 use crate::pieces::goban::*;
 use crate::pieces::stones::Color::{Black, White};
-use crate::pieces::stones::{Color, Stone, EMPTY};
-use crate::pieces::util::coord::{corner_points, is_coord_valid, two_to_1dim, Coord, Size};
+use crate::pieces::stones::{Color, MaybeColor, Stone};
+use crate::pieces::util::coord::{
+    corner_points, is_coord_valid, neighbor_coords, one_to_2dim, two_to_1dim, valid_coords, Coord,
+    Size,
+};
 use crate::pieces::Nat;
+use crate::rules::clock::Clock;
+use crate::rules::events::{GameEvent, ListenerId, Listeners};
+use crate::rules::game_info::GameInfo;
+use crate::rules::history::Snapshots;
+use crate::rules::handicap::{fixed_handicap_points, free_handicap_points};
 use crate::rules::EndGame::{Draw, WinnerByScore};
 use crate::rules::Rule;
-use crate::rules::{EndGame, GobanSizes, IllegalRules, Move, ScoreRules};
+use crate::rules::{EndGame, GobanSizes, IllegalRules, Move, MoveOutcome, ScoreRules};
 use crate::rules::{PlayError, CHINESE};
 
 /// Most important struct of the library, it's the entry point.
 /// It represents a Game of Go.
-#[derive(Clone, Getters, CopyGetters, Setters, Debug)]
-pub struct Game {
+/// The board is a `Goban` by default, see the trait `Board` for the others.
+#[derive(Clone, Getters, CopyGetters, MutGetters, Setters, Debug)]
+pub struct Game<B: Board = Goban> {
     #[get = "pub"]
-    pub(super) goban: Goban,
+    pub(super) goban: B,
 
     #[get_copy = "pub"]
     pub(super) passes: u32,
@@ -36,50 +50,161 @@ pub struct Game {
     #[get_copy = "pub"]
     pub(super) handicap: u32,
 
-    #[cfg(feature = "history")]
+    /// The handicap stones placed before the first move.
+    #[get = "pub"]
+    pub(super) handicap_points: Vec<Coord>,
+
+    /// All the moves played with the color of the player.
+    #[get = "pub"]
+    pub(super) moves: Vec<(Color, Move)>,
+
+    /// Information about the game, players, event etc...
     #[get = "pub"]
-    pub(super) history: Vec<Goban>,
+    #[get_mut = "pub"]
+    #[set = "pub"]
+    pub(super) info: GameInfo,
+
+    /// Snapshots of the goban for rebuilding the past positions, see the module `history`.
+    pub(super) snapshots: Snapshots<B>,
 
     #[get = "pub"]
     pub(super) last_hash: u64,
 
     pub(super) hashes: HashedSet<u64>,
 
+    /// The point the player can't play because of the ko.
+    #[get_copy = "pub"]
     pub(super) ko_point: Option<Coord>,
+
+    /// None if the game isn't timed.
+    #[get = "pub"]
+    #[set = "pub"]
+    pub(super) clock: Option<Clock>,
+
+    pub(super) listeners: Listeners,
 }
 
 impl Game {
     /// Crates a new game for playing Go
+    ///
+    /// # Panics
+    ///
+    /// If a side of the goban is bigger than 19, see `try_new` for a checked version.
     pub fn new(size: GobanSizes, rule: Rule) -> Self {
-        let (h, w) = size.into();
-        let goban = Goban::new(size.into());
+        Game::with_board(Goban::new(size.into()), rule)
+    }
+
+    /// Crates a new game, but checks the size of the goban first.
+    ///
+    /// # Errors
+    ///
+    /// InvalidSize if a side of the goban is 0 or bigger than 19.
+    pub fn try_new(size: GobanSizes, rule: Rule) -> Result<Self, PlayError> {
+        if size.is_valid() {
+            Ok(Game::new(size, rule))
+        } else {
+            Err(PlayError::InvalidSize)
+        }
+    }
+}
+
+impl<B: Board> Game<B> {
+    /// Creates a game on a board, the stones of the board are kept like handicap stones.
+    /// Black plays first.
+    /// The games read from SGF or from a diagram are on a `Goban`, `convert` moves them on
+    /// another board.
+    /// # Example
+    /// ```
+    /// use goban::pieces::goban::Goban;
+    /// use goban::pieces::stones::Color;
+    /// use goban::rules::game::Game;
+    /// use goban::rules::{Move, JAPANESE};
+    ///
+    /// let mut goban = Goban::new((9, 9));
+    /// goban.push((2, 2), Color::Black);
+    /// let mut game = Game::with_board(goban, JAPANESE);
+    /// game.play(Move::Play(2, 3));
+    /// assert_eq!(game.goban().number_of_stones(), (2, 0));
+    /// ```
+    pub fn with_board(goban: B, rule: Rule) -> Self {
+        let (h, w) = goban.size();
         let length = h as usize * w as usize;
-        #[cfg(feature = "history")]
-        let history = Vec::with_capacity(length);
-        let prisoners = (0, 0);
-        let handicap = 0;
         let hashes = HashedSet::with_capacity_and_hasher(length, HashBuildHasher::default());
         Self {
             goban,
             turn: Color::Black,
-            prisoners,
+            prisoners: (0, 0),
             passes: 0,
-            #[cfg(feature = "history")]
-            history,
+            snapshots: Snapshots::default(),
             outcome: None,
             rule,
-            handicap,
+            handicap: 0,
+            handicap_points: vec![],
+            moves: Vec::with_capacity(length),
+            info: GameInfo::default(),
             hashes,
             last_hash: 0,
             ko_point: None,
+            clock: None,
+            listeners: Listeners::default(),
         }
     }
-}
 
-impl Game {
+    /// Returns the same game on an other kind of board, the moves are played again.
+    /// # Example
+    /// ```
+    /// use goban::rules::game::Game;
+    /// use goban::rules::{GobanSizes, Move, JAPANESE};
+    ///
+    /// let mut game = Game::new(GobanSizes::Nine, JAPANESE);
+    /// game.play(Move::Play(2, 2)).play(Move::Play(2, 3));
+    /// let copy: Game = game.convert();
+    /// assert_eq!(copy.goban(), game.goban());
+    /// ```
+    pub fn convert<C: Board>(&self) -> Game<C> {
+        let first = self
+            .position_at(0)
+            .expect("The position before the first move");
+        let size = first.size();
+        let mut goban = C::new(size);
+        let points = first
+            .to_vec()
+            .into_iter()
+            .take(size.0 as usize * size.1 as usize);
+        for (index, color) in points.enumerate() {
+            if let Some(color) = color {
+                goban.push(one_to_2dim(size, index), color);
+            }
+        }
+        let mut game = Game::with_board(goban, self.rule);
+        game.turn = self.moves.first().map_or(self.turn, |&(color, _)| color);
+        game.handicap = self.handicap;
+        game.handicap_points = self.handicap_points.clone();
+        game.info = self.info.clone();
+        game.clock = self.clock;
+        game.outcome = self.outcome;
+        game.ko_point = if self.moves.is_empty() {
+            self.ko_point
+        } else {
+            None
+        };
+        for &(color, m) in &self.moves {
+            if game.passes >= 2 {
+                game.resume();
+            }
+            game.turn = color;
+            game.play(m);
+        }
+        game.turn = self.turn;
+        game
+    }
+
     /// Resume the game when to players have passed, and want to continue.
     #[inline]
     pub fn resume(&mut self) {
+        if self.passes >= 2 {
+            self.listeners.notify(GameEvent::Resumed);
+        }
         self.passes = 0;
     }
 
@@ -137,17 +262,7 @@ impl Game {
 
     /// Get all moves on all empty intersections.
     pub fn pseudo_legals_vec(&self) -> Vec<Coord> {
-        let size = self.size();
-        let mut vec = Vec::with_capacity((size.0 * size.1) as usize);
-        let board = self.goban.board();
-        for i in 0..size.0 as Nat {
-            for j in 0..size.1 as Nat {
-                if board[two_to_1dim(size, (i, j))].is_none() {
-                    vec.push((i, j));
-                }
-            }
-        }
-        vec
+        self.goban.get_empty_coords().collect()
     }
 
     /// Returns a list with legals moves. from the rule specified in at the creation.
@@ -169,55 +284,191 @@ impl Game {
     ///
     /// # Panics
     ///
-    /// If the coordinates of the move are outside the board.
+    /// If the coordinates of the move are outside the board, or if the game is paused and
+    /// the move is a pass. Use `try_play` with untrusted moves.
     pub fn play(&mut self, play: Move) -> &mut Self {
+        if self.listeners.is_empty() {
+            self.play_move(play, None);
+        } else {
+            self.play_detailed(play);
+        }
+        self
+    }
+
+    /// Same as `play` but returns what happened on the goban, the captures, the ko etc...
+    /// For a pass or a resignation only the hash is set.
+    ///
+    /// # Panics
+    ///
+    /// The same as `play`.
+    pub fn play_detailed(&mut self, play: Move) -> MoveOutcome {
+        let color = self.turn;
+        let mut captured = vec![];
+        self.play_move(play, Some(&mut captured));
+
+        let goban = &self.goban;
+        let outcome = if let Move::Play(x, y) = play {
+            let self_atari =
+                goban.get_color((x, y)).is_some() && goban.chain_liberties((x, y)) == 1;
+            let mut atari_chains: Vec<(usize, Coord)> = valid_coords((x, y), goban.size())
+                .into_iter()
+                .filter(|&point| {
+                    goban.get_color(point) == Some(!color) && goban.chain_liberties(point) == 1
+                })
+                .filter_map(|point| goban.chain_id(point).map(|id| (id, point)))
+                .collect();
+            atari_chains.sort_unstable();
+            atari_chains.dedup_by_key(|&mut (id, _)| id);
+            MoveOutcome {
+                captured,
+                ko_point: self.ko_point,
+                self_atari,
+                ataris: atari_chains
+                    .into_iter()
+                    .map(|(_, point)| goban.chain_stones(point))
+                    .collect(),
+                hash: goban.zobrist_hash(),
+            }
+        } else {
+            MoveOutcome {
+                hash: goban.zobrist_hash(),
+                ..Default::default()
+            }
+        };
+        if !self.listeners.is_empty() {
+            self.notify_move(color, play, &outcome);
+        }
+        outcome
+    }
+
+    /// Plays the move, the stones of the captured chains are added to `captured` if asked.
+    fn play_move(&mut self, play: Move, captured: Option<&mut Vec<Vec<Coord>>>) {
+        self.snapshots.record(self.moves.len(), &self.goban);
+        self.moves.push((self.turn, play));
         match play {
             Move::Pass => {
                 assert!(self.passes < 2, "This game is already paused");
                 self.turn = !self.turn;
                 self.passes += 1;
-                self
-            }
-            Move::Play(x, y) => {
-                let hash = self.goban.zobrist_hash();
-                self.last_hash = hash;
-                self.hashes.insert(hash);
-                #[cfg(feature = "history")]
-                self.history.push(self.goban.clone());
-                let (dead_rens, added_ren) = self.goban.push_wth_feedback((x, y), self.turn);
-                self.ko_point = None;
-                self.remove_captured_stones(&dead_rens, added_ren);
-                //self.prisoners = self.remove_captured_stones();
-                self.turn = !self.turn;
-                self.passes = 0;
-                self
             }
+            Move::Play(x, y) => self.play_stone((x, y), captured),
             Move::Resign(player) => {
                 self.outcome = Some(EndGame::WinnerByResign(player));
-                self
             }
         }
     }
 
-    /// This methods plays a move then return the hash of the goban simulated,
-    /// used in legals for fast move simulation in Super Ko situations.
-    pub fn play_for_verification(&self, (x, y): Coord) -> u64 {
-        let mut test_goban = self.goban.clone();
-        let (dead_go_strings, added_ren) = test_goban.push_wth_feedback((x, y), self.turn);
-        test_goban.remove_captured_stones_aux(
-            self.turn,
-            !self.rule.flag_illegal.contains(IllegalRules::SUICIDE),
-            self.prisoners,
-            &dead_go_strings,
-            added_ren,
-        );
-        test_goban.zobrist_hash()
+    /// Sends the events of the move played by `color` to the listeners.
+    fn notify_move(&self, color: Color, play: Move, outcome: &MoveOutcome) {
+        let listeners = &self.listeners;
+        match play {
+            Move::Play(x, y) => {
+                listeners.notify(GameEvent::StonePlaced(Stone {
+                    coord: (x, y),
+                    color,
+                }));
+                if !outcome.captured.is_empty() {
+                    listeners.notify(GameEvent::ChainsCaptured {
+                        color: !color,
+                        chains: outcome.captured.clone(),
+                    });
+                }
+                if let Some(ko_point) = outcome.ko_point {
+                    listeners.notify(GameEvent::KoEstablished(ko_point));
+                }
+            }
+            Move::Pass => {
+                listeners.notify(GameEvent::Pass(color));
+                if self.passes >= 2 {
+                    listeners.notify(GameEvent::ScoringPhase);
+                }
+            }
+            Move::Resign(winner) => {
+                listeners.notify(GameEvent::Resign(winner));
+                listeners.notify(GameEvent::GameOver(EndGame::WinnerByResign(winner)));
+            }
+        }
+    }
+
+    /// Adds a listener called after every change of the game, see the module `events`.
+    /// The listeners aren't kept by the clones of the game.
+    pub fn subscribe(
+        &mut self,
+        listener: impl Fn(&GameEvent) + Send + Sync + 'static,
+    ) -> ListenerId {
+        self.listeners.add(Arc::new(listener))
+    }
+
+    /// Removes a listener, returns false if it wasn't subscribed.
+    pub fn unsubscribe(&mut self, id: ListenerId) -> bool {
+        self.listeners.remove(id)
+    }
+
+    /// Puts the stone of the player who has the turn and removes the captured chains.
+    /// The stones of the captured chains are added to `captured` if asked.
+    fn play_stone(&mut self, point: Coord, captured: Option<&mut Vec<Vec<Coord>>>) {
+        let hash = self.goban.zobrist_hash();
+        self.last_hash = hash;
+        self.hashes.insert(hash);
+        let (stones, ko_point) = self.goban.push_with_captures(point, self.turn, captured);
+        match self.turn {
+            Black => self.prisoners.0 += stones,
+            White => self.prisoners.1 += stones,
+        }
+        self.ko_point = ko_point;
+        self.turn = !self.turn;
+        self.passes = 0;
+    }
+
+    /// Method to play a move with the time spent by the player on it.
+    /// If the player runs out of time, the move isn't played and the game is won by the
+    /// opponent. Without clock it's the same as `play`.
+    pub fn play_with_time(&mut self, play: Move, elapsed: Duration) -> &mut Self {
+        let turn = self.turn;
+        if let Some(clock) = &mut self.clock {
+            if !clock.spend(turn, elapsed) {
+                let outcome = EndGame::WinnerByTime(!turn);
+                self.outcome = Some(outcome);
+                self.listeners.notify(GameEvent::GameOver(outcome));
+                return self;
+            }
+        }
+        self.play(play)
+    }
+
+    /// Returns the hash of the goban after the move of the player, used in legals for fast
+    /// move simulation in Super Ko situations. The goban isn't copied.
+    #[inline]
+    pub fn play_for_verification(&self, point: Coord) -> u64 {
+        self.goban.hash_after(point, self.turn)
     }
 
+    /// Plays the move for the player `color` even if it's not his turn.
+    /// If the move is illegal the turn isn't changed.
+    ///
+    /// # Errors
+    ///
+    /// The same as `try_play`.
     pub fn try_play_color(&mut self, color: Color, play: Move) -> Result<&mut Self, PlayError> {
-        self.turn = color;
-        // TODO: we don't undo color if we fail.
-        return self.try_play(play);
+        let turn = std::mem::replace(&mut self.turn, color);
+        if let Err(e) = self.check_move(play) {
+            self.turn = turn;
+            return Err(e);
+        }
+        Ok(self.play(play))
+    }
+
+    /// Plays the move of the player `color`, it must be his turn. A resignation can be played
+    /// at any time, the color is the one of the winner like in `Move::Resign`.
+    ///
+    /// # Errors
+    ///
+    /// WrongTurn if it's not the turn of the player, else the same as `try_play`.
+    pub fn try_play_as(&mut self, color: Color, play: Move) -> Result<&mut Self, PlayError> {
+        match play {
+            Move::Play(..) | Move::Pass if color != self.turn => Err(PlayError::WrongTurn),
+            _ => self.try_play(play),
+        }
     }
 
     /// Method to play but it verifies if the play is legal or not.
@@ -227,37 +478,71 @@ impl Game {
     /// If the move is a suicide Move return SuicideMove
     /// If the move is a Ko Move returns Ko
     /// if point is already filled then return PointNotEmpty
+    /// If the point is outside the goban then return OutOfBounds
     /// If the game is paused then return GamePaused
+    /// If the game is finished by resignation or time then return GameOver
     pub fn try_play(&mut self, play: Move) -> Result<&mut Self, PlayError> {
-        if self.passes == 2 {
+        self.check_move(play)?;
+        Ok(self.play(play))
+    }
+
+    /// Returns the error `try_play` would return for this move.
+    fn check_move(&self, play: Move) -> Result<(), PlayError> {
+        if self.outcome.is_some() {
+            Err(PlayError::GameOver)
+        } else if self.passes >= 2 {
             Err(PlayError::GamePaused)
+        } else if let Move::Play(x, y) = play {
+            self.check_point((x, y)).map_or(Ok(()), Err)
         } else {
-            match play {
-                Move::Play(x, y) => {
-                    if self.goban.get_color((x, y)) != EMPTY {
-                        Err(PlayError::PointNotEmpty)
-                    } else if let Some(c) = self.check_point((x as Nat, y as Nat)) {
-                        Err(c)
-                    } else {
-                        Ok(self.play(play))
-                    }
-                }
-                Move::Pass | Move::Resign(_) => Ok(self.play(play)),
-            }
+            Ok(())
         }
     }
 
     /// Put the handicap stones on the goban.
     /// This put the turn for white but doesn't update the komi.
+    ///
+    /// # Panics
+    ///
+    /// If a move was already played, the stones wouldn't be in the history of the game.
     pub fn put_handicap(&mut self, points: &[Coord]) {
-        self.handicap = points.len() as u32;
+        assert!(
+            self.moves.is_empty(),
+            "The handicap is put before the first move"
+        );
+        self.handicap_points.extend_from_slice(points);
+        self.handicap = self.handicap_points.len() as u32;
         points.iter().for_each(|&coord| {
             self.goban.push(coord, Color::Black);
         });
         self.turn = Color::White;
     }
 
-    /// Calculates score. with prisoners and komi.
+    /// Put the stones of a fixed handicap, with the standard placement.
+    /// This put the turn for white but doesn't update the komi.
+    /// Returns an error if the handicap isn't valid for the goban or a move was already played.
+    pub fn fixed_handicap(&mut self, handicap: u32) -> Result<&mut Self, String> {
+        if !self.moves.is_empty() {
+            return Err("The handicap is put before the first move".to_string());
+        }
+        let points = fixed_handicap_points(self.size(), handicap)?;
+        self.put_handicap(&points);
+        Ok(self)
+    }
+
+    /// Put the stones of a free handicap, the placement is chosen by the library.
+    /// This put the turn for white but doesn't update the komi.
+    /// Returns an error if the handicap isn't valid for the goban or a move was already played.
+    pub fn free_handicap(&mut self, handicap: u32) -> Result<&mut Self, String> {
+        if !self.moves.is_empty() {
+            return Err("The handicap is put before the first move".to_string());
+        }
+        let points = free_handicap_points(self.size(), handicap)?;
+        self.put_handicap(&points);
+        Ok(self)
+    }
+
+    /// Calculates score. with prisoners, komi and handicap compensation.
     /// Dependant of the rule in the game.
     #[inline]
     pub fn calculate_score(&self) -> (f32, f32) {
@@ -280,6 +565,7 @@ impl Game {
         }
         if rule.contains(ScoreRules::KOMI) {
             white_score += self.komi();
+            white_score += self.rule.handicap_compensation.points(self.handicap);
         }
 
         (black_score, white_score)
@@ -287,27 +573,31 @@ impl Game {
 
     /// Returns true if the stone played in that point will capture another
     /// string.
+    #[inline]
     pub fn will_capture(&self, point: Coord) -> bool {
         self.goban
-            .get_neighbors_chains(point)
-            .filter(|go_str| go_str.color != self.turn)
+            .neighbor_chains(point)
             // if an enemy string has only liberty it's a capture move
-            .any(|go_str| go_str.is_atari())
+            .any(|(color, liberties)| color != self.turn && liberties == 1)
     }
 
-    /// Test if a point is legal or not for the current player,
+    /// Test if a point is legal or not for the current player, a point outside the goban is
+    /// OutOfBounds.
     #[inline]
     pub fn check_point(&self, point: Coord) -> Option<PlayError> {
         self.check_point_by(point, self.rule.flag_illegal)
     }
 
     /// Test if a point is legal or not by the rule passed in parameter.
+    #[inline]
     pub fn check_point_by(&self, point: Coord, illegal_rules: IllegalRules) -> Option<PlayError> {
         let stone = Stone {
             coord: point,
             color: self.turn,
         };
-        if !self.goban.get_point(point).is_empty() {
+        if !is_coord_valid(self.goban.size(), point) {
+            Some(PlayError::OutOfBounds)
+        } else if self.goban.get_color(point).is_some() {
             Some(PlayError::PointNotEmpty)
         } else if illegal_rules.contains(IllegalRules::KO) && self.check_ko(stone) {
             Some(PlayError::Ko)
@@ -322,7 +612,18 @@ impl Game {
         }
     }
 
+    // Colors of the points next to the point.
+    #[inline]
+    fn neighbor_colors(&self, point: Coord) -> impl Iterator<Item = MaybeColor> + '_ {
+        let size = self.goban.size();
+        neighbor_coords(point)
+            .into_iter()
+            .filter(move |&p| is_coord_valid(size, p))
+            .map(move |p| self.goban.get_color(p))
+    }
+
     // Return the number of allied corner and off board corners.
+    #[inline]
     fn helper_check_eye(&self, point: (Nat, Nat), color: Color) -> (Nat, Nat) {
         let mut corner_ally = 0;
         let mut corner_off_board = 0;
@@ -349,6 +650,7 @@ impl Game {
     /// ```
     /// This function is only used for performance checking in the rules,
     /// and not for checking is a point is really an eye !
+    #[inline]
     pub fn check_eye(&self, Stone { coord, color }: Stone) -> bool {
         // if the intersection is not empty
         if self.goban.get_color(coord).is_some() {
@@ -356,11 +658,7 @@ impl Game {
         }
 
         // if he doesn't have the cross then return false
-        if self
-            .goban
-            .get_neighbors_points(coord)
-            .any(|s| s.color != Some(color))
-        {
+        if self.neighbor_colors(coord).any(|c| c != Some(color)) {
             return false;
         }
 
@@ -374,16 +672,12 @@ impl Game {
             for s in corner_points(coord)
                 .into_iter()
                 .filter(move |p| is_coord_valid(self.goban.size(), *p))
-                .filter_map(move |c| Some(self.goban.get_point(c)).filter(|p| p.is_empty()))
+                .filter(move |&p| self.goban.get_color(p).is_none())
             {
-                if self
-                    .goban
-                    .get_neighbors_stones(s.coord)
-                    .any(move |s| s.color != color)
-                {
+                if self.neighbor_colors(s).any(|c| c == Some(!color)) {
                     return false;
                 }
-                let (ca, cof) = self.helper_check_eye(s.coord, color);
+                let (ca, cof) = self.helper_check_eye(s, color);
                 let c = ca + cof;
                 if c == 3 || c == 2 {
                     return true;
@@ -395,11 +689,13 @@ impl Game {
 
     /// Test if a play is ko.
     /// If the goban is in the configuration of two plays ago returns true
+    #[inline]
     pub fn check_ko(&self, stone: Stone) -> bool {
         self.ko_point == Some(stone.coord)
     }
 
     /// Rule of the super Ko, if any before configuration was already played then return true.
+    #[inline]
     pub fn check_superko(&self, stone: Stone) -> bool {
         if self.last_hash == 0 || self.hashes.len() <= 2 || !self.will_capture(stone.coord) {
             false
@@ -414,42 +710,93 @@ impl Game {
     /// Add a stone to the board an then test if the stone or stone group is
     /// dead.
     /// Returns true if the move is a suicide
+    #[inline]
     pub fn check_suicide(&self, stone: Stone) -> bool {
         if self.goban.has_liberties(stone.coord) {
             false
         } else {
             !self
                 .goban
-                .get_neighbors_chains(stone.coord)
-                .any(|neighbor_go_string| {
-                    if neighbor_go_string.color == stone.color {
+                .neighbor_chains(stone.coord)
+                .any(|(color, liberties)| {
+                    let atari = liberties == 1;
+                    if color == stone.color {
                         // Connecting with an other string which is not in danger
-                        !neighbor_go_string.is_atari()
+                        !atari
                     } else {
                         // Capture move
-                        neighbor_go_string.is_atari()
+                        atari
                     }
                 })
         }
     }
 
+    /// Returns, in a RowMajor Policy, the number of the move that placed each stone on the
+    /// goban. The moves are counted from 1, passes included. A captured stone loses its
+    /// number, and the point gets the number of the next move played on it.
+    /// The handicap stones and the stones set up on the goban have no number.
+    /// # Example
+    /// ```
+    /// use goban::rules::game::Game;
+    /// use goban::rules::{GobanSizes, Move, JAPANESE};
+    ///
+    /// let mut game = Game::new(GobanSizes::Nine, JAPANESE);
+    /// game.play(Move::Play(0, 0)).play(Move::Pass).play(Move::Play(0, 1));
+    /// let numbers = game.move_numbers();
+    /// assert_eq!(numbers[0], Some(1));
+    /// assert_eq!(numbers[1], Some(3));
+    /// assert_eq!(numbers[2], None);
+    /// ```
+    pub fn move_numbers(&self) -> Vec<Option<u32>> {
+        let size = self.size();
+        let mut numbers = vec![None; size.0 as usize * size.1 as usize];
+        // The last move played on a point with a stone is the one that placed it.
+        for (number, &(_, m)) in self.moves.iter().enumerate() {
+            if let Move::Play(line, column) = m {
+                numbers[two_to_1dim(size, (line, column))] = Some(number as u32 + 1);
+            }
+        }
+        for (number, color) in numbers.iter_mut().zip(self.goban.to_vec()) {
+            if color.is_none() {
+                *number = None;
+            }
+        }
+        numbers
+    }
+
     /// Displays the internal board.
     pub fn display_goban(&self) {
         println!("{}", self.goban)
     }
+}
 
-    #[inline]
-    fn remove_captured_stones(&mut self, dead_chains: &[ChainIdx], added_chain: ChainIdx) {
-        let res = self.goban.remove_captured_stones_aux(
-            self.turn,
-            !self.rule.flag_illegal.contains(IllegalRules::SUICIDE),
-            self.prisoners,
-            dead_chains,
-            added_chain,
-        );
-        self.prisoners = res.0;
-        self.ko_point = res.1;
-    }
+/// Creates a game from a text diagram, with the side to move and the ko point of the diagram.
+/// Black moves if the diagram doesn't tell it. See the module `diagram` for the format.
+/// # Example
+/// ```
+/// use goban::rules::game::game_from_diagram;
+/// use goban::rules::{JAPANESE, PlayError, Move};
+///
+/// let mut game = game_from_diagram("
+///     . X O .
+///     X * X O
+///     . X O .
+///     White to play
+/// ", JAPANESE).unwrap();
+/// assert_eq!(game.check_point((1, 1)), Some(PlayError::Ko));
+/// ```
+pub fn game_from_diagram(diagram: &str, rule: Rule) -> Result<Game, String> {
+    let diagram: Diagram = diagram.parse()?;
+    let (height, width) = diagram.size;
+    let mut game = Game::new(GobanSizes::Custom(height as usize, width as usize), rule);
+    game.goban = diagram.to_goban()?;
+    game.turn = diagram.turn.unwrap_or(Color::Black);
+    game.ko_point = diagram.ko_point;
+    // The position of the diagram is the first of the game for the super ko.
+    let hash = game.goban.zobrist_hash();
+    game.last_hash = hash;
+    game.hashes.insert(hash);
+    Ok(game)
 }
 
 impl Default for Game {
@@ -21,34 +21,43 @@
 use crate::pieces::goban::Goban;
 use crate::pieces::stones::Color;
 use crate::pieces::stones::Color::White;
-use crate::pieces::util::coord::{Coord, Size};
-use crate::rules::{CHINESE, EndGame, Move, Rule};
+use crate::pieces::util::coord::{is_coord_valid, Coord, Size};
+use crate::rules::clock::{Clock, TimeControl};
+use crate::rules::game_info::GameInfo;
+use crate::rules::handicap::{fixed_handicap_points, free_handicap_points};
+use crate::rules::{CHINESE, EndGame, Move, PlayError, Rule};
 use crate::rules::game::Game;
 
+enum Handicap {
+    Points(Vec<Coord>),
+    Fixed(u32),
+    Free(u32),
+}
+
 pub struct GameBuilder {
     size: Size,
-    black_player: String,
-    white_player: String,
+    info: GameInfo,
     rule: Rule,
     komi: Option<f32>,
-    handicap_points: Vec<Coord>,
+    handicap: Handicap,
     turn: Option<Color>,
     moves: Vec<Move>,
     outcome: Option<EndGame>,
+    time_control: Option<TimeControl>,
 }
 
 impl GameBuilder {
     fn new() -> GameBuilder {
         GameBuilder {
             size: (19, 19),
-            black_player: "".to_string(),
-            white_player: "".to_string(),
-            handicap_points: vec![],
+            info: GameInfo::default(),
+            handicap: Handicap::Points(vec![]),
             rule: CHINESE,
             komi: None,
             turn: None,
             moves: vec![],
             outcome: None,
+            time_control: None,
         }
     }
 
@@ -64,7 +73,21 @@ impl GameBuilder {
 
     /// Overrides the turn because it's a game with handicap. So White begins.
     pub fn handicap(&mut self, points: &[Coord]) -> &mut Self {
-        self.handicap_points = points.to_vec();
+        self.handicap = Handicap::Points(points.to_vec());
+        self
+    }
+
+    /// Handicap with the standard placement, the points are computed at the build with the
+    /// size of the goban.
+    pub fn fixed_handicap(&mut self, handicap: u32) -> &mut Self {
+        self.handicap = Handicap::Fixed(handicap);
+        self
+    }
+
+    /// Handicap with a placement chosen by the library, the points are computed at the build
+    /// with the size of the goban.
+    pub fn free_handicap(&mut self, handicap: u32) -> &mut Self {
+        self.handicap = Handicap::Free(handicap);
         self
     }
 
@@ -84,7 +107,7 @@ impl GameBuilder {
     }
 
     pub fn black_player(&mut self, black_player_name: &str) -> &mut Self {
-        self.black_player = black_player_name.to_string();
+        self.info.black_player = Some(black_player_name.to_string());
         self
     }
 
@@ -94,16 +117,45 @@ impl GameBuilder {
     }
 
     pub fn white_player(&mut self, white_player_name: &str) -> &mut Self {
-        self.white_player = white_player_name.to_string();
+        self.info.white_player = Some(white_player_name.to_string());
         self
     }
 
-    pub fn build(&mut self) -> Result<Game, String> {
-        let mut goban: Goban = Goban::new(self.size);
+    /// Sets the information of the game, it overrides the players names.
+    pub fn info(&mut self, info: GameInfo) -> &mut Self {
+        self.info = info;
+        self
+    }
+
+    /// Adds a clock to the game.
+    pub fn time_control(&mut self, time_control: TimeControl) -> &mut Self {
+        self.time_control = Some(time_control);
+        self
+    }
 
-        goban.push_many(&self.handicap_points, Color::Black);
+    /// Creates the game and replays the moves, the moves aren't checked against the rule but
+    /// they must be on empty points of the goban.
+    ///
+    /// # Errors
+    ///
+    /// If the size is invalid, or if a handicap stone or a move isn't on an empty point.
+    pub fn build(&mut self) -> Result<Game, String> {
+        let (height, width) = self.size;
+        let mut goban: Goban = Goban::try_new(self.size)
+            .map_err(|e| format!("Cannot create a {height}x{width} goban: {e}"))?;
+
+        let handicap_points = match &self.handicap {
+            Handicap::Points(points) => points.clone(),
+            &Handicap::Fixed(handicap) => fixed_handicap_points(self.size, handicap)?,
+            &Handicap::Free(handicap) => free_handicap_points(self.size, handicap)?,
+        };
+        for &point in &handicap_points {
+            goban
+                .try_push(point, Color::Black)
+                .map_err(|e| format!("Invalid handicap stone {point:?}: {e}"))?;
+        }
 
-        if !self.handicap_points.is_empty() && self.turn.is_none() {
+        if !handicap_points.is_empty() && self.turn.is_none() {
             self.turn = Some(White)
         }
 
@@ -118,15 +170,29 @@ impl GameBuilder {
             outcome: self.outcome,
             turn: self.turn.unwrap_or(Color::Black),
             rule: self.rule,
-            handicap: self.handicap_points.len() as u32,
-            #[cfg(feature = "history")]
-            history: vec![],
+            handicap: handicap_points.len() as u32,
+            handicap_points,
+            moves: Vec::with_capacity(self.moves.len()),
+            info: self.info.clone(),
+            snapshots: Default::default(),
             hashes: Default::default(),
             last_hash: 0,
             ko_point: None,
+            clock: self.time_control.map(Clock::new),
+            listeners: Default::default(),
         };
 
         for &m in &self.moves {
+            match m {
+                Move::Play(x, y) if !is_coord_valid(g.size(), (x, y)) => {
+                    return Err(format!("Invalid move {m}: {}", PlayError::OutOfBounds));
+                }
+                Move::Play(x, y) if g.goban.get_color((x, y)).is_some() => {
+                    return Err(format!("Invalid move {m}: {}", PlayError::PointNotEmpty));
+                }
+                Move::Pass if g.passes >= 2 => g.resume(),
+                _ => (),
+            }
             g.play(m);
         }
 
@@ -0,0 +1,118 @@
+//! Module with the information of a game record, the players, the event etc...
+
+/// Information about a game, stored in the root node of a SGF file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameInfo {
+    /// PB
+    pub black_player: Option<String>,
+    /// PW
+    pub white_player: Option<String>,
+    /// BR
+    pub black_rank: Option<String>,
+    /// WR
+    pub white_rank: Option<String>,
+    /// BT
+    pub black_team: Option<String>,
+    /// WT
+    pub white_team: Option<String>,
+    /// DT
+    pub date: Option<String>,
+    /// EV
+    pub event: Option<String>,
+    /// RO
+    pub round: Option<String>,
+    /// PC
+    pub place: Option<String>,
+    /// SO
+    pub source: Option<String>,
+    /// GN
+    pub game_name: Option<String>,
+    /// Other properties of the root node, (identifier, value).
+    pub properties: Vec<(String, String)>,
+}
+
+impl GameInfo {
+    /// Returns the SGF identifiers with the values of the information known.
+    fn fields(&self) -> [(&'static str, &Option<String>); 12] {
+        [
+            ("PB", &self.black_player),
+            ("PW", &self.white_player),
+            ("BR", &self.black_rank),
+            ("WR", &self.white_rank),
+            ("BT", &self.black_team),
+            ("WT", &self.white_team),
+            ("DT", &self.date),
+            ("EV", &self.event),
+            ("RO", &self.round),
+            ("PC", &self.place),
+            ("SO", &self.source),
+            ("GN", &self.game_name),
+        ]
+    }
+
+    /// Sets the information by its SGF identifier, unknown identifiers are stored in
+    /// `properties`.
+    pub fn set_sgf_property(&mut self, identifier: &str, value: &str) {
+        let field = match identifier {
+            "PB" => &mut self.black_player,
+            "PW" => &mut self.white_player,
+            "BR" => &mut self.black_rank,
+            "WR" => &mut self.white_rank,
+            "BT" => &mut self.black_team,
+            "WT" => &mut self.white_team,
+            "DT" => &mut self.date,
+            "EV" => &mut self.event,
+            "RO" => &mut self.round,
+            "PC" => &mut self.place,
+            "SO" => &mut self.source,
+            "GN" => &mut self.game_name,
+            _ => {
+                self.properties
+                    .push((identifier.to_string(), value.to_string()));
+                return;
+            }
+        };
+        *field = Some(value.to_string());
+    }
+
+    /// Returns the information as SGF properties. Ex: `PB[Shusaku]PW[Inseki]`
+    pub fn to_sgf(&self) -> String {
+        let mut buff = String::new();
+        let known = self
+            .fields()
+            .into_iter()
+            .filter_map(|(identifier, value)| value.as_deref().map(|v| (identifier, v)));
+        let others = self
+            .properties
+            .iter()
+            .map(|(identifier, value)| (identifier.as_str(), value.as_str()));
+        for (identifier, value) in known.chain(others) {
+            buff.push_str(identifier);
+            buff.push('[');
+            buff.push_str(&escape_sgf(value));
+            buff.push(']');
+        }
+        buff
+    }
+}
+
+/// Escapes the characters that can't be in a SGF value.
+pub(crate) fn escape_sgf(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(']', "\\]")
+}
+
+/// Reverse of `escape_sgf`.
+pub(crate) fn unescape_sgf(value: &str) -> String {
+    let mut res = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(escaped) = chars.next() {
+                res.push(escaped);
+            }
+        } else {
+            res.push(c);
+        }
+    }
+    res
+}
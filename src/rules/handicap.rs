@@ -0,0 +1,95 @@
+//! Module for the placement of the handicap stones.
+//! The fixed placement follows the GTP specification, the same used by the SGF files.
+
+use crate::pieces::util::coord::{Coord, Size};
+
+/// Returns the maximum number of stones of a fixed handicap for this size of goban.
+/// Boards with a side smaller than 7 don't have fixed handicap.
+pub fn max_fixed_handicap((height, width): Size) -> u32 {
+    if height.min(width) < 7 {
+        0
+    } else if height % 2 == 0 || width % 2 == 0 || height.min(width) == 7 {
+        4
+    } else {
+        9
+    }
+}
+
+/// Returns the points of a fixed handicap of `handicap` stones, with the standard placement on
+/// the star points. The stones are on the fourth line for boards of 13 and bigger, else on
+/// the third line.
+pub fn fixed_handicap_points(size: Size, handicap: u32) -> Result<Vec<Coord>, String> {
+    let max = max_fixed_handicap(size);
+    if handicap < 2 || handicap > max {
+        return Err(format!(
+            "A fixed handicap of {handicap} stones is not possible on a {}x{} goban",
+            size.0, size.1
+        ));
+    }
+    let (height, width) = size;
+    let edge = if height.min(width) >= 13 { 3 } else { 2 };
+    let (top, bottom, middle_line) = (edge, height - 1 - edge, (height - 1) / 2);
+    let (left, right, middle_column) = (edge, width - 1 - edge, (width - 1) / 2);
+
+    // Bottom left, top right, top left, bottom right.
+    let mut points = vec![(bottom, left), (top, right), (top, left), (bottom, right)];
+    points.truncate(handicap.min(4) as usize);
+    if handicap >= 6 {
+        points.extend([(middle_line, left), (middle_line, right)]);
+    }
+    if handicap >= 8 {
+        points.extend([(bottom, middle_column), (top, middle_column)]);
+    }
+    if handicap % 2 == 1 && handicap >= 5 {
+        points.push((middle_line, middle_column));
+    }
+    Ok(points)
+}
+
+/// Returns the points of a free handicap of `handicap` stones chosen by the library.
+/// It uses the fixed placement as long as possible, then adds the stones the farthest possible
+/// from the others, preferably away from the edges. The placement is deterministic.
+pub fn free_handicap_points(size: Size, handicap: u32) -> Result<Vec<Coord>, String> {
+    let (height, width) = size;
+    let length = height as u32 * width as u32;
+    if handicap < 2 || handicap >= length {
+        return Err(format!(
+            "A free handicap of {handicap} stones is not possible on a {}x{} goban",
+            height, width
+        ));
+    }
+    let mut points =
+        fixed_handicap_points(size, handicap.min(max_fixed_handicap(size))).unwrap_or_default();
+    let margin = if height.min(width) >= 13 { 2 } else { 1 };
+    let is_central = |(line, column): Coord| {
+        line >= margin && column >= margin && line + margin < height && column + margin < width
+    };
+
+    while points.len() < handicap as usize {
+        let distance_to_stones = |(line, column): Coord| {
+            points
+                .iter()
+                .map(|&(l, c)| {
+                    let (dl, dc) = (line.abs_diff(l) as u32, column.abs_diff(c) as u32);
+                    dl * dl + dc * dc
+                })
+                .min()
+                .unwrap_or(u32::MAX)
+        };
+        let best = (0..height)
+            .flat_map(|line| (0..width).map(move |column| (line, column)))
+            .filter(|point| !points.contains(point))
+            // Central points first, then the farthest from the stones, then the first in
+            // row major order.
+            .max_by_key(|&point| {
+                (
+                    is_central(point),
+                    distance_to_stones(point),
+                    std::cmp::Reverse(point),
+                )
+            })
+            .expect("There is always an empty point");
+        points.push(best);
+    }
+    Ok(points)
+}
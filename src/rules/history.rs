@@ -0,0 +1,154 @@
+//! Module for the past positions of a game.
+//!
+//! The history is the move log of the game plus a snapshot of the goban every
+//! `SNAPSHOT_INTERVAL` moves. A position is rebuilt from the closest snapshot before it, so it
+//! costs at most `SNAPSHOT_INTERVAL` moves to replay.
+//! # Example
+//! ```
+//! use goban::rules::game::Game;
+//! use goban::rules::{GobanSizes, Move, JAPANESE};
+//!
+//! let mut game = Game::new(GobanSizes::Nine, JAPANESE);
+//! game.play(Move::Play(0, 0)).play(Move::Play(0, 1)).play(Move::Play(1, 0));
+//! assert_eq!(game.position_at(1).unwrap().number_of_stones(), (1, 0));
+//! assert_eq!(game.history().len(), 4);
+//! assert_eq!(game.history().next_back().as_ref(), Some(game.goban()));
+//! ```
+
+use std::sync::Arc;
+
+use crate::pieces::board::Board;
+use crate::pieces::goban::Goban;
+use crate::pieces::stones::Color;
+use crate::rules::game::Game;
+use crate::rules::Move;
+
+/// Number of moves between two snapshots of the goban.
+pub const SNAPSHOT_INTERVAL: usize = 32;
+
+/// Snapshots of the goban taken by the game, shared by the clones of the game. The list is
+/// shared too, so a clone of the game only counts a reference, the list is copied when a
+/// clone takes a new snapshot.
+#[derive(Debug, Clone)]
+pub(crate) struct Snapshots<B>(Arc<Vec<Arc<B>>>);
+
+impl<B> Default for Snapshots<B> {
+    fn default() -> Self {
+        Snapshots(Arc::new(vec![]))
+    }
+}
+
+impl<B: Board> Snapshots<B> {
+    /// Takes a snapshot of the goban if the move `number` (counted from 0) starts a new
+    /// interval.
+    #[inline]
+    pub(crate) fn record(&mut self, number: usize, goban: &B) {
+        if number == self.0.len() * SNAPSHOT_INTERVAL {
+            self.push(goban);
+        }
+    }
+
+    /// Kept out of `record`, the copy of the goban needs a big stack frame that every move
+    /// would pay for.
+    #[cold]
+    #[inline(never)]
+    fn push(&mut self, goban: &B) {
+        Arc::make_mut(&mut self.0).push(Arc::new(goban.clone()));
+    }
+
+    fn before(&self, number: usize) -> Option<&B> {
+        self.0.get(number / SNAPSHOT_INTERVAL).map(|goban| &**goban)
+    }
+}
+
+/// Plays the move on the goban with the captures, without checking it.
+fn replay<B: Board>(goban: &mut B, (color, play): (Color, Move)) {
+    if let Move::Play(x, y) = play {
+        goban.push_with_captures((x, y), color, None);
+    }
+}
+
+impl<B: Board> Game<B> {
+    /// Returns the goban after the `number` first moves, the position 0 is the goban before
+    /// the first move. None if less moves were played.
+    pub fn position_at(&self, number: usize) -> Option<B> {
+        if number > self.moves.len() {
+            return None;
+        }
+        let Some(snapshot) = self.snapshots.before(number) else {
+            // No moves played since the goban was set up.
+            return Some(self.goban.clone());
+        };
+        let mut goban = snapshot.clone();
+        let start = number - number % SNAPSHOT_INTERVAL;
+        for &m in &self.moves[start..number] {
+            replay(&mut goban, m);
+        }
+        Some(goban)
+    }
+
+    /// Returns an iterator over the positions of the game, from the goban before the first
+    /// move to the current goban. It's double ended, and going forward replays only one move
+    /// by position.
+    pub fn history(&self) -> Positions<'_, B> {
+        Positions {
+            game: self,
+            front: 0,
+            back: self.moves.len() + 1,
+            next: None,
+        }
+    }
+}
+
+/// Iterator over the positions of a game, created by `Game::history`.
+#[derive(Debug, Clone)]
+pub struct Positions<'a, B: Board = Goban> {
+    game: &'a Game<B>,
+    front: usize,
+    back: usize,
+    /// The position at `front` if it's already computed.
+    next: Option<B>,
+}
+
+impl<B: Board> Iterator for Positions<'_, B> {
+    type Item = B;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        let position = match self.next.take() {
+            Some(position) => position,
+            None => self.game.position_at(self.front)?,
+        };
+        if self.front + 1 < self.back {
+            let mut next = position.clone();
+            replay(&mut next, self.game.moves[self.front]);
+            self.next = Some(next);
+        }
+        self.front += 1;
+        Some(position)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<B: Board> DoubleEndedIterator for Positions<'_, B> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        if self.front == self.back {
+            if let Some(position) = self.next.take() {
+                return Some(position);
+            }
+        }
+        self.game.position_at(self.back)
+    }
+}
+
+impl<B: Board> ExactSizeIterator for Positions<'_, B> {}
@@ -1,15 +1,28 @@
 //! Module for ruling in the game of go.
 
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
+use crate::notation::Notation;
 use crate::pieces::Nat;
 use crate::pieces::stones::Color;
 use crate::pieces::util::coord::{Coord, Size};
 
+pub mod clock;
 #[cfg(feature = "deadstones")]
 mod dead_stones;
+pub mod events;
 pub mod game;
 pub mod game_builder;
+pub mod game_info;
+pub mod handicap;
+pub mod history;
+#[cfg(feature = "montecarlo")]
+mod playout;
+#[cfg(feature = "montecarlo")]
+pub mod score_estimation;
+pub mod score_report;
+mod sensei_bridge;
 mod sgf_bridge;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -31,13 +44,29 @@ impl From<GobanSizes> for Size {
     }
 }
 
-impl From<usize> for GobanSizes {
-    fn from(x: usize) -> Self {
+/// Square goban of this size, the sizes other than 9, 13 and 19 are custom.
+impl TryFrom<usize> for GobanSizes {
+    type Error = PlayError;
+
+    fn try_from(x: usize) -> Result<Self, Self::Error> {
         match x {
-            9 => GobanSizes::Nine,
-            13 => GobanSizes::Thirteen,
-            19 => GobanSizes::Nineteen,
-            _ => panic!("Not implemented for others size than 9,13,19"),
+            9 => Ok(GobanSizes::Nine),
+            13 => Ok(GobanSizes::Thirteen),
+            19 => Ok(GobanSizes::Nineteen),
+            1..=19 => Ok(GobanSizes::Custom(x, x)),
+            _ => Err(PlayError::InvalidSize),
+        }
+    }
+}
+
+impl GobanSizes {
+    /// True if a goban of this size can be created, the sides are between 1 and 19.
+    pub const fn is_valid(self) -> bool {
+        match self {
+            GobanSizes::Custom(height, width) => {
+                matches!(height, 1..=19) && matches!(width, 1..=19)
+            }
+            _ => true,
         }
     }
 }
@@ -56,6 +85,43 @@ impl From<Coord> for Move {
     }
 }
 
+/// Displays the move with the SGF notation which doesn't depend on the size of the goban.
+/// Ex: "dp", "pass", "W+R". A point after the z, which SGF can't write, is displayed like
+/// Debug.
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (*self, Notation::Sgf.format_move((26, 26), *self)) {
+            (Move::Pass, _) => write!(f, "pass"),
+            (_, Some(s)) => write!(f, "{s}"),
+            (m, None) => write!(f, "{m:?}"),
+        }
+    }
+}
+
+/// Parses a move with the SGF notation, "pass" and "" are passes. Ex: "dp", "pass", "W+R"
+impl FromStr for Move {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Notation::Sgf.parse_move((19, 19), s)
+    }
+}
+
+/// What happened on the goban after a move, returned by `Game::play_detailed`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MoveOutcome {
+    /// The stones of each captured chain.
+    pub captured: Vec<Vec<Coord>>,
+    /// The point the opponent can't retake because of the ko.
+    pub ko_point: Option<Coord>,
+    /// True if the chain of the stone played has only one liberty.
+    pub self_atari: bool,
+    /// The stones of each opponent chain next to the stone played that are in atari.
+    pub ataris: Vec<Vec<Coord>>,
+    /// The zobrist hash of the goban after the move.
+    pub hash: u64,
+}
+
 #[derive(Debug, Clone, PartialEq, Copy)]
 pub enum EndGame {
     WinnerByScore(Color, f32),
@@ -65,6 +131,23 @@ pub enum EndGame {
     Draw,
 }
 
+/// Displays the outcome like the result of a SGF file. Ex: "B+3.5", "W+R", "Draw"
+impl Display for EndGame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let color = |c: Color| match c {
+            Color::Black => 'B',
+            Color::White => 'W',
+        };
+        match *self {
+            EndGame::WinnerByScore(c, score) => write!(f, "{}+{}", color(c), score),
+            EndGame::WinnerByResign(c) => write!(f, "{}+R", color(c)),
+            EndGame::WinnerByTime(c) => write!(f, "{}+T", color(c)),
+            EndGame::WinnerByForfeit(c) => write!(f, "{}+F", color(c)),
+            EndGame::Draw => write!(f, "Draw"),
+        }
+    }
+}
+
 impl EndGame {
     /// Return the winner of the game, if none the game is draw.
     #[inline]
@@ -86,8 +169,38 @@ pub enum PlayError {
     GamePaused,
     FillEye,
     PointNotEmpty,
+    /// The point isn't on the goban.
+    OutOfBounds,
+    /// The game is finished by resignation, time or forfeit.
+    GameOver,
+    /// The move is played by the player who doesn't have the turn.
+    WrongTurn,
+    /// The size of the goban isn't supported.
+    InvalidSize,
+    /// The rule isn't implemented.
+    UnsupportedRule,
 }
 
+impl Display for PlayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            PlayError::Ko => "the move retakes a ko",
+            PlayError::Suicide => "the move is a suicide",
+            PlayError::GamePaused => "the game is paused after two passes",
+            PlayError::FillEye => "the move fills an eye",
+            PlayError::PointNotEmpty => "the point is not empty",
+            PlayError::OutOfBounds => "the point is outside the goban",
+            PlayError::GameOver => "the game is over",
+            PlayError::WrongTurn => "it's not the turn of this player",
+            PlayError::InvalidSize => "the size of the goban is not supported",
+            PlayError::UnsupportedRule => "the rule is not supported",
+        };
+        write!(f, "{message}")
+    }
+}
+
+impl std::error::Error for PlayError {}
+
 type FlagUInt = u32;
 bitflags! {
     /// Behaviours not permitted, if the flag is up then the move is not legal.
@@ -114,11 +227,60 @@ bitflags! {
     }
 }
 
+/// Points given to White to compensate the handicap stones.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HandicapCompensation {
+    /// No compensation.
+    None,
+    /// One point per handicap stone.
+    PerStone,
+    /// One point per handicap stone except the first.
+    PerStoneMinusOne,
+}
+
+impl HandicapCompensation {
+    /// Returns the points given to White for this handicap.
+    #[inline]
+    pub const fn points(self, handicap: u32) -> f32 {
+        match self {
+            HandicapCompensation::None => 0.,
+            HandicapCompensation::PerStone => handicap as f32,
+            HandicapCompensation::PerStoneMinusOne => handicap.saturating_sub(1) as f32,
+        }
+    }
+}
+
+/// The rule of a game. Use `Rule::new` or start from `JAPANESE` or `CHINESE` to build one, a
+/// struct literal breaks when a field is added.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Rule {
     pub komi: f32,
     pub flag_illegal: IllegalRules,
     pub flag_score: ScoreRules,
+    pub handicap_compensation: HandicapCompensation,
+}
+
+impl Rule {
+    /// Creates a rule without handicap compensation.
+    pub const fn new(komi: f32, flag_illegal: IllegalRules, flag_score: ScoreRules) -> Self {
+        Rule {
+            komi,
+            flag_illegal,
+            flag_score,
+            handicap_compensation: HandicapCompensation::None,
+        }
+    }
+
+    /// Returns the rule with the points given to White for the handicap.
+    pub const fn with_handicap_compensation(
+        self,
+        handicap_compensation: HandicapCompensation,
+    ) -> Self {
+        Rule {
+            handicap_compensation,
+            ..self
+        }
+    }
 }
 
 pub static JAPANESE: Rule = Rule {
@@ -129,6 +291,7 @@ pub static JAPANESE: Rule = Rule {
     flag_score: ScoreRules::from_bits_truncate(
         ScoreRules::KOMI.bits() | ScoreRules::PRISONNERS.bits(),
     ),
+    handicap_compensation: HandicapCompensation::None,
 };
 
 pub static CHINESE: Rule = Rule {
@@ -137,6 +300,7 @@ pub static CHINESE: Rule = Rule {
         IllegalRules::KO.bits() | IllegalRules::SUPERKO.bits() | IllegalRules::SUICIDE.bits(),
     ),
     flag_score: ScoreRules::from_bits_truncate(ScoreRules::KOMI.bits() | ScoreRules::STONES.bits()),
+    handicap_compensation: HandicapCompensation::PerStone,
 };
 
 impl FromStr for Rule {
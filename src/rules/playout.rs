@@ -0,0 +1,43 @@
+//! Random playouts used by the Monte-Carlo estimations.
+
+use rand::prelude::SliceRandom;
+use rand::Rng;
+
+use crate::pieces::board::Board;
+use crate::pieces::stones::Stone;
+use crate::rules::game::Game;
+use crate::rules::Move;
+
+impl<B: Board> Game<B> {
+    /// Plays a random move that is legal and doesn't fill an eye of the player. Pass if there
+    /// is none.
+    pub(crate) fn random_playout_move(&self, rng: &mut impl Rng) -> Move {
+        let mut v: Vec<_> = self.pseudo_legals().collect();
+        v.shuffle(rng);
+        v.into_iter()
+            .filter(|&point| self.check_point(point).is_none())
+            .find(|&point| {
+                !self.check_eye(Stone {
+                    coord: point,
+                    color: self.turn,
+                })
+            })
+            .map_or(Move::Pass, Move::from)
+    }
+
+    /// Plays the game until the end with random moves. The passes and the outcome of the game
+    /// are ignored, so it works on finished games too.
+    pub(crate) fn playout(&self, rng: &mut impl Rng) -> Game<B> {
+        let mut game = self.clone();
+        game.passes = 0;
+        game.outcome = None;
+        // Caps the playout, because without super ko the game could loop forever.
+        let mut remaining_moves = 4 * game.size().0 as usize * game.size().1 as usize;
+        while !game.is_over() && remaining_moves != 0 {
+            let m = game.random_playout_move(rng);
+            game.play(m);
+            remaining_moves -= 1;
+        }
+        game
+    }
+}
@@ -0,0 +1,87 @@
+//! Monte-Carlo score estimation, works on unfinished games.
+
+use rand::Rng;
+
+use crate::pieces::board::Board;
+use crate::pieces::stones::Color;
+use crate::pieces::util::coord::two_to_1dim;
+use crate::rules::game::Game;
+
+/// Result of a Monte-Carlo score estimation.
+/// The margins are positive when Black is ahead.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreEstimation {
+    /// Mean of the score margins (black score - white score) of the playouts.
+    pub mean: f32,
+    /// Standard deviation of the score margins of the playouts.
+    pub std_dev: f32,
+    /// Proportion of the playouts won by Black.
+    pub black_win_probability: f32,
+    /// For each intersection in a RowMajor Policy, the frequency of the playouts where Black
+    /// owns the point minus the frequency where White owns it. From -1. to 1.
+    pub ownership: Vec<f32>,
+}
+
+impl ScoreEstimation {
+    /// Returns the color with the most chances of winning, None if it's balanced.
+    pub fn leader(&self) -> Option<Color> {
+        if self.black_win_probability > 0.5 {
+            Some(Color::Black)
+        } else if self.black_win_probability < 0.5 {
+            Some(Color::White)
+        } else {
+            None
+        }
+    }
+}
+
+impl<B: Board> Game<B> {
+    /// Estimates the score by playing `n_playouts` random games from the current position.
+    /// Each playout is scored with the rule of the game. The passes and the outcome of the
+    /// game are ignored, so it works on resigned or unfinished games.
+    /// The playouts use the generator passed in parameter, so a seeded generator gives
+    /// reproducible results.
+    /// At least one playout is played, a count of 0 is estimated like a count of 1.
+    pub fn estimate_score(&self, n_playouts: usize, rng: &mut impl Rng) -> ScoreEstimation {
+        let n_playouts = n_playouts.max(1);
+        let size = self.size();
+        let mut ownership = vec![0i32; size.0 as usize * size.1 as usize];
+        let mut margins = Vec::with_capacity(n_playouts);
+        let mut black_wins = 0;
+        for _ in 0..n_playouts {
+            let final_state = self.playout(rng);
+            let (black_score, white_score) = final_state.calculate_score();
+            let margin = black_score - white_score;
+            if margin > 0. {
+                black_wins += 1;
+            }
+            margins.push(margin);
+
+            // The territories are only computed point by point on a `Goban`.
+            let goban = final_state.goban().to_goban();
+            for stone in goban.get_stones() {
+                ownership[two_to_1dim(size, stone.coord)] += match stone.color {
+                    Color::Black => 1,
+                    Color::White => -1,
+                };
+            }
+            let (black_territory, white_territory) = goban.get_territories();
+            for point in black_territory {
+                ownership[two_to_1dim(size, point.coord)] += 1;
+            }
+            for point in white_territory {
+                ownership[two_to_1dim(size, point.coord)] -= 1;
+            }
+        }
+
+        let n = n_playouts as f32;
+        let mean = margins.iter().sum::<f32>() / n;
+        let variance = margins.iter().map(|m| (m - mean).powi(2)).sum::<f32>() / n;
+        ScoreEstimation {
+            mean,
+            std_dev: variance.sqrt(),
+            black_win_probability: black_wins as f32 / n,
+            ownership: ownership.into_iter().map(|x| x as f32 / n).collect(),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Module explaining the score of a finished game point by point.
+
+use std::fmt::{Display, Formatter};
+
+use crate::pieces::board::Board;
+use crate::pieces::stones::Color;
+use crate::pieces::territory::Ownership;
+use crate::pieces::util::coord::Coord;
+use crate::rules::game::Game;
+use crate::rules::{EndGame, Rule, ScoreRules};
+
+/// Details of the score of a game. The pairs are (Black, White).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreReport {
+    /// The rule used for the score.
+    pub rule: Rule,
+    /// Empty points and dead stones surrounded by each color.
+    pub territory: (u32, u32),
+    /// Living stones on the goban, counted with the area scoring.
+    pub stones: (u32, u32),
+    /// Stones captured during the game by each color.
+    pub prisoners: (u32, u32),
+    /// Dead stones of each color removed at the end of the game, they are added to the
+    /// prisoners of the opponent.
+    pub dead_stones: (u32, u32),
+    pub komi: f32,
+    pub handicap_compensation: f32,
+    /// The final scores.
+    pub score: (f32, f32),
+}
+
+impl ScoreReport {
+    /// Returns the winner by score, or a draw.
+    pub fn outcome(&self) -> EndGame {
+        let (black, white) = self.score;
+        if (black - white).abs() < f32::EPSILON {
+            EndGame::Draw
+        } else if black > white {
+            EndGame::WinnerByScore(Color::Black, black - white)
+        } else {
+            EndGame::WinnerByScore(Color::White, white - black)
+        }
+    }
+
+    /// Returns the black score minus the white score.
+    #[inline]
+    pub fn margin(&self) -> f32 {
+        self.score.0 - self.score.1
+    }
+}
+
+/// Prints the report like a result slip, only the lines used by the rule are printed.
+/// The prisoners and the dead stones are in the column of the player who gets the points.
+impl Display for ScoreReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let scoring = if self.rule.flag_score.contains(ScoreRules::STONES) {
+            "area"
+        } else {
+            "territory"
+        };
+        writeln!(f, "Scoring: {scoring}")?;
+        writeln!(f, "{:<14}{:>8}{:>8}", "", "Black", "White")?;
+        writeln!(
+            f,
+            "{:<14}{:>8}{:>8}",
+            "Territory", self.territory.0, self.territory.1
+        )?;
+        if self.rule.flag_score.contains(ScoreRules::STONES) {
+            writeln!(
+                f,
+                "{:<14}{:>8}{:>8}",
+                "Stones", self.stones.0, self.stones.1
+            )?;
+        }
+        if self.rule.flag_score.contains(ScoreRules::PRISONNERS) {
+            writeln!(
+                f,
+                "{:<14}{:>8}{:>8}",
+                "Prisoners", self.prisoners.0, self.prisoners.1
+            )?;
+            writeln!(
+                f,
+                "{:<14}{:>8}{:>8}",
+                "Dead stones", self.dead_stones.1, self.dead_stones.0
+            )?;
+        }
+        if self.rule.flag_score.contains(ScoreRules::KOMI) {
+            writeln!(f, "{:<14}{:>8}{:>8}", "Komi", "", self.komi)?;
+            if self.handicap_compensation != 0. {
+                writeln!(
+                    f,
+                    "{:<14}{:>8}{:>8}",
+                    "Handicap", "", self.handicap_compensation
+                )?;
+            }
+        }
+        writeln!(f, "{:<14}{:>8}{:>8}", "Total", self.score.0, self.score.1)?;
+        write!(f, "Result: {}", self.outcome())
+    }
+}
+
+impl<B: Board> Game<B> {
+    /// Returns the details of the score with the rule of the game.
+    /// The chains with a stone in `dead_stones` are removed before counting.
+    /// Without dead stones the score is the same as `calculate_score`.
+    /// The ownership of the points is computed on a copy of the board in a `Goban`.
+    pub fn score_report(&self, dead_stones: &[Coord]) -> ScoreReport {
+        let rule = self.rule();
+        let mut territory = (0, 0);
+        let mut stones = (0, 0);
+        for ownership in self.goban().to_goban().ownership_map(dead_stones) {
+            match ownership {
+                Ownership::Territory(Color::Black) => territory.0 += 1,
+                Ownership::Territory(Color::White) => territory.1 += 1,
+                Ownership::Stone(Color::Black) => stones.0 += 1,
+                Ownership::Stone(Color::White) => stones.1 += 1,
+                Ownership::Dame | Ownership::SekiLiberty => (),
+            }
+        }
+        let (black_stones, white_stones) = self.goban().number_of_stones();
+        let dead_stones = (black_stones - stones.0, white_stones - stones.1);
+        let prisoners = self.prisoners();
+        let komi = self.komi();
+        let handicap_compensation = rule.handicap_compensation.points(self.handicap());
+
+        let mut score = (territory.0 as f32, territory.1 as f32);
+        if rule.flag_score.contains(ScoreRules::PRISONNERS) {
+            score.0 += (prisoners.0 + dead_stones.1) as f32;
+            score.1 += (prisoners.1 + dead_stones.0) as f32;
+        }
+        if rule.flag_score.contains(ScoreRules::STONES) {
+            score.0 += stones.0 as f32;
+            score.1 += stones.1 as f32;
+        }
+        if rule.flag_score.contains(ScoreRules::KOMI) {
+            score.1 += komi + handicap_compensation;
+        }
+        ScoreReport {
+            rule,
+            territory,
+            stones,
+            prisoners,
+            dead_stones,
+            komi,
+            handicap_compensation,
+            score,
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use crate::pieces::board::Board;
+use crate::pieces::diagram::Diagram;
+use crate::pieces::sensei::SenseiDiagram;
+use crate::pieces::stones::Color;
+use crate::rules::game::Game;
+use crate::rules::{GobanSizes, Move, Rule};
+
+impl Game {
+    /// Creates a game from a Sensei's Library diagram, the stones of the diagram are set up on
+    /// the goban and the numbered moves are played.
+    /// The game is on a `Goban`, see `Game::convert` for the other boards.
+    /// # Example
+    /// ```
+    /// use goban::rules::game::Game;
+    /// use goban::rules::{Move, JAPANESE};
+    /// use goban::pieces::stones::Color;
+    ///
+    /// let game = Game::from_sensei(
+    ///     "$$W9
+    ///      $$ +-------------------+
+    ///      $$ | . . . . . . . . . |
+    ///      $$ | . . . . . . . . . |
+    ///      $$ | . . X O . . , . . |
+    ///      $$ | . . X O 1 . . . . |
+    ///      $$ | . . X O 2 . . . . |
+    ///      $$ | . . , . . . , . . |
+    ///      $$ | . . . . . . . . . |
+    ///      $$ | . . . . . . . . . |
+    ///      $$ | . . . . . . . . . |
+    ///      $$ +-------------------+",
+    ///     JAPANESE,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(game.moves(), &[(Color::White, Move::Play(3, 4)), (Color::Black, Move::Play(4, 4))]);
+    /// assert_eq!(game.turn(), Color::White);
+    /// ```
+    /// # Errors
+    /// If the diagram is invalid, or if a numbered move is illegal.
+    pub fn from_sensei(diagram: &str, rule: Rule) -> Result<Game, String> {
+        let sensei: SenseiDiagram = diagram.parse()?;
+        let (height, width) = sensei.size;
+        let mut game = Game::new(GobanSizes::Custom(height as usize, width as usize), rule);
+        game.goban = Diagram {
+            size: sensei.size,
+            points: sensei.points,
+            turn: None,
+            ko_point: None,
+        }
+        .to_goban()?;
+        game.turn = sensei.first_color;
+        for (number, point) in (sensei.first_move..).zip(sensei.moves) {
+            let m = point.map_or(Move::Pass, Move::from);
+            if game.passes >= 2 {
+                game.resume();
+            }
+            game.try_play(m)
+                .map_err(|e| format!("Invalid move {number} of the diagram: {e}"))?;
+        }
+        Ok(game)
+    }
+}
+
+impl<B: Board> Game<B> {
+    /// Returns the Sensei's Library diagram of the game, with the last moves numbered. At most
+    /// ten moves are numbered.
+    pub fn to_sensei(&self, numbered_moves: u32) -> String {
+        let moves: Vec<(Color, Move)> = self
+            .moves
+            .iter()
+            .copied()
+            .take_while(|(_, m)| !matches!(m, Move::Resign(_)))
+            .collect();
+        let numbered = (numbered_moves.min(10) as usize).min(moves.len());
+        let start = moves.len() - numbered;
+        if numbered == 0 {
+            return SenseiDiagram::from_goban(&self.goban, self.turn).to_string();
+        }
+        let before = self
+            .position_at(start)
+            .expect("The numbered moves are in the game");
+        let diagram = SenseiDiagram {
+            first_move: start as u32 + 1,
+            moves: moves[start..]
+                .iter()
+                .map(|&(_, m)| match m {
+                    Move::Play(line, column) => Some((line, column)),
+                    _ => None,
+                })
+                .collect(),
+            ..SenseiDiagram::from_goban(&before, moves[start].0)
+        };
+        diagram.to_string()
+    }
+}
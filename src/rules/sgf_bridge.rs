@@ -1,15 +1,24 @@
+use std::time::Duration;
+
 use sgf_parser::{Action, Outcome, RuleSet, SgfToken};
 
+use crate::notation::Notation;
+use crate::pieces::board::Board;
 use crate::pieces::Nat;
 use crate::pieces::stones::Color;
 use crate::pieces::util::coord::Coord;
-use crate::rules::{CHINESE, EndGame, JAPANESE, Move, Rule};
+use crate::rules::clock::TimeControl;
+use crate::rules::{CHINESE, EndGame, JAPANESE, Move, PlayError, Rule};
 use crate::rules::game::Game;
 use crate::rules::game_builder::GameBuilder;
+use crate::rules::game_info::{unescape_sgf, GameInfo};
+use crate::rules::handicap::max_fixed_handicap;
 
 type SgfColor = sgf_parser::Color;
 
 impl Game {
+    /// Reads a game from SGF, the game is on a `Goban`, see `Game::convert` for the other
+    /// boards.
     pub fn from_sgf(sgf_str: &str) -> Result<Self, String> {
         let game_tree = match sgf_parser::parse(sgf_str) {
             Ok(game) => Ok(game),
@@ -19,6 +28,14 @@ impl Game {
         let mut first = true;
         let mut moves = vec![];
         let mut handicap: Vec<Coord> = vec![];
+        let mut info = GameInfo::default();
+        let mut size = (19, 19);
+        let mut handicap_number = 0;
+        let mut main_time = None;
+        let mut overtime = None;
+        // Last remaining time and periods recorded for (Black, White).
+        let mut times_left = (None, None);
+        let mut periods_left = (0, 0);
 
         for node in game_tree.iter() {
             if first {
@@ -30,8 +47,10 @@ impl Game {
                             game_builder.komi(*komi);
                         }
                         SgfToken::Size(x, y) => {
-                            game_builder.size((*x as u8, *y as u8));
+                            size = (*y as u8, *x as u8);
+                            game_builder.size(size);
                         }
+                        SgfToken::Handicap(h) => handicap_number = *h,
                         SgfToken::Result(o) => {
                             game_builder.outcome((*o).into());
                         }
@@ -39,10 +58,32 @@ impl Game {
                             color,
                             coordinate: (x, y),
                         } if *color == SgfColor::Black => {
-                            handicap.push(((*x - 1) as Nat, (*y - 1) as Nat));
+                            handicap.push(((*y - 1) as Nat, (*x - 1) as Nat));
                         }
                         SgfToken::Rule(rule) => {
-                            game_builder.rule(rule.clone().into());
+                            let rule = Rule::try_from(rule.clone()).map_err(|e| {
+                                format!("Cannot import the rule {}: {e}", rule.to_string())
+                            })?;
+                            game_builder.rule(rule);
+                        }
+                        SgfToken::TimeLimit(time) => main_time = Some(*time),
+                        SgfToken::Overtime(o) => overtime = Some(o.clone()),
+                        SgfToken::PlayerName { color, name } => match color {
+                            SgfColor::Black => info.set_sgf_property("PB", &unescape_sgf(name)),
+                            SgfColor::White => info.set_sgf_property("PW", &unescape_sgf(name)),
+                        },
+                        SgfToken::PlayerRank { color, rank } => match color {
+                            SgfColor::Black => info.set_sgf_property("BR", &unescape_sgf(rank)),
+                            SgfColor::White => info.set_sgf_property("WR", &unescape_sgf(rank)),
+                        },
+                        SgfToken::Date(date) => info.set_sgf_property("DT", &unescape_sgf(date)),
+                        SgfToken::Event(event) => info.set_sgf_property("EV", &unescape_sgf(event)),
+                        SgfToken::Place(place) => info.set_sgf_property("PC", &unescape_sgf(place)),
+                        SgfToken::GameName(name) => {
+                            info.set_sgf_property("GN", &unescape_sgf(name))
+                        }
+                        SgfToken::Unknown((identifier, value)) => {
+                            info.set_sgf_property(identifier, &unescape_sgf(value))
                         }
                         //TODO another options
                         _ => (),
@@ -54,20 +95,124 @@ impl Game {
                 if let SgfToken::Move { action, .. } = token {
                     moves.push((*action).into());
                 }
+                for token in &node.tokens {
+                    match token {
+                        SgfToken::Time { color, time } => match color {
+                            SgfColor::Black => times_left.0 = Some(*time),
+                            SgfColor::White => times_left.1 = Some(*time),
+                        },
+                        SgfToken::MovesRemaining { color, moves } => match color {
+                            SgfColor::Black => periods_left.0 = *moves,
+                            SgfColor::White => periods_left.1 = *moves,
+                        },
+                        _ => (),
+                    }
+                }
             }
         }
-        game_builder.handicap(&handicap);
+        if let Some(main_time) = main_time {
+            // An unsupported overtime doesn't prevent the import of the game.
+            if let Ok(time_control) = TimeControl::from_sgf(main_time, overtime.as_deref()) {
+                game_builder.time_control(time_control);
+            }
+        }
+        if handicap.is_empty() && handicap_number >= 2 {
+            // HA without AB, the stones are on the standard points.
+            if handicap_number <= max_fixed_handicap(size) {
+                game_builder.fixed_handicap(handicap_number);
+            } else {
+                game_builder.free_handicap(handicap_number);
+            }
+        } else {
+            game_builder.handicap(&handicap);
+        }
+        game_builder.info(info);
         game_builder.moves(&moves);
-        game_builder.build()
+        let mut game = game_builder.build()?;
+        if let Some(clock) = &mut game.clock {
+            for (color, time_left, periods) in [
+                (Color::Black, times_left.0, periods_left.0),
+                (Color::White, times_left.1, periods_left.1),
+            ] {
+                if let Some(time_left) = time_left {
+                    clock.set_time_left(color, Duration::from_secs(time_left as u64), periods);
+                }
+            }
+        }
+        Ok(game)
     }
 }
 
-impl From<RuleSet> for Rule {
-    fn from(r: RuleSet) -> Self {
+impl<B: Board> Game<B> {
+    /// Exports the game in the SGF format, with the information of the game, the handicap
+    /// stones and the moves played.
+    pub fn to_sgf(&self) -> String {
+        let size = self.size();
+        let (height, width) = size;
+        let mut buff = String::from("(;FF[4]GM[1]CA[UTF-8]");
+        if height == width {
+            buff.push_str(&format!("SZ[{width}]"));
+        } else {
+            buff.push_str(&format!("SZ[{width}:{height}]"));
+        }
+        buff.push_str(&format!("KM[{}]", self.komi()));
+        if self.rule.flag_illegal == JAPANESE.flag_illegal
+            && self.rule.flag_score == JAPANESE.flag_score
+        {
+            buff.push_str("RU[Japanese]");
+        } else if self.rule.flag_illegal == CHINESE.flag_illegal
+            && self.rule.flag_score == CHINESE.flag_score
+        {
+            buff.push_str("RU[Chinese]");
+        }
+        if self.handicap != 0 {
+            buff.push_str(&format!("HA[{}]", self.handicap));
+        }
+        if !self.handicap_points.is_empty() {
+            buff.push_str("AB");
+            for &point in &self.handicap_points {
+                let point = Notation::Sgf
+                    .format_coord(size, point)
+                    .expect("The handicap stones are on the goban");
+                buff.push_str(&format!("[{point}]"));
+            }
+        }
+        buff.push_str(&self.info.to_sgf());
+        if let Some(clock) = &self.clock {
+            buff.push_str(&clock.time_control().to_sgf());
+        }
+        if let Some(outcome) = self.outcome() {
+            buff.push_str(&format!("RE[{outcome}]"));
+        }
+        for &(color, m) in &self.moves {
+            let color = match color {
+                Color::Black => "B",
+                Color::White => "W",
+            };
+            match m {
+                Move::Play(x, y) => {
+                    let point = Notation::Sgf
+                        .format_coord(size, (x, y))
+                        .expect("The moves are played on the goban");
+                    buff.push_str(&format!(";{color}[{point}]"));
+                }
+                Move::Pass => buff.push_str(&format!(";{color}[]")),
+                Move::Resign(_) => (),
+            }
+        }
+        buff.push(')');
+        buff
+    }
+}
+
+impl TryFrom<RuleSet> for Rule {
+    type Error = PlayError;
+
+    fn try_from(r: RuleSet) -> Result<Self, Self::Error> {
         match r {
-            RuleSet::Japanese => JAPANESE,
-            RuleSet::Chinese => CHINESE,
-            _ => panic!("The rule {} is not implemented yet !", r.to_string()),
+            RuleSet::Japanese => Ok(JAPANESE),
+            RuleSet::Chinese => Ok(CHINESE),
+            _ => Err(PlayError::UnsupportedRule),
         }
     }
 }
@@ -2,16 +2,30 @@
 mod tests {
     use std::collections::HashSet;
     use std::mem;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
+    use rand::rngs::StdRng;
     use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
 
+    use goban::notation::Notation;
     use goban::pieces::goban::Goban;
     use goban::pieces::stones::{Color, EMPTY, Point, Stone};
     use goban::pieces::util::CircularRenIter;
+    use goban::pieces::util::coord::{one_to_2dim, two_to_1dim};
     use goban::pieces::zobrist::index_zobrist;
-    use goban::rules::{EndGame, GobanSizes, Move};
+    use goban::render::star_points;
+    use goban::render::kifu::KifuPrinter;
+    use goban::render::svg::{Markup, SvgRenderer, Theme};
+    use goban::render::terminal::{Orientation, TerminalRenderer};
+    use goban::rules::clock::{Clock, TimeControl};
+    use goban::rules::events::GameEvent;
+    use goban::rules::game_info::GameInfo;
+    use goban::rules::handicap::{fixed_handicap_points, free_handicap_points};
+    use goban::rules::{EndGame, GobanSizes, HandicapCompensation, Move, PlayError, Rule};
     use goban::rules::{CHINESE, JAPANESE};
-    use goban::rules::game::Game;
+    use goban::rules::game::{game_from_diagram, Game};
 
     #[test]
     fn sizes() {
@@ -26,6 +40,32 @@ mod tests {
         println!("{}", g.pretty_string());
     }
 
+    #[test]
+    fn coords_non_square_goban() {
+        let size = (7, 11);
+        for index in 0..77 {
+            let coord = one_to_2dim(size, index);
+            assert!(
+                coord.0 < 7 && coord.1 < 11,
+                "{coord:?} outside of the goban"
+            );
+            assert_eq!(goban::one2dim!(size, index), coord);
+            assert_eq!(two_to_1dim(size, coord), index);
+        }
+        // The rows are 11 points long.
+        assert_eq!(two_to_1dim(size, (1, 0)), 11);
+        assert_eq!(one_to_2dim(size, 76), (6, 10));
+
+        let mut g = Goban::new(size);
+        g.push((6, 10), Color::Black).push((0, 10), Color::White);
+        assert_eq!(g.get_color((6, 10)), Some(Color::Black));
+        assert_eq!(g.get_color((0, 10)), Some(Color::White));
+        assert_eq!(g.get_empty_coords().count(), 75);
+        assert!(g
+            .get_empty_coords()
+            .all(|coord| coord.0 < 7 && coord.1 < 11));
+    }
+
     #[test]
     fn goban_new_array() {
         let mut g = Goban::new(GobanSizes::Nineteen.into());
@@ -472,7 +512,7 @@ mod tests {
         };
         goban.push_stone(s);
         println!("{}", goban.pretty_string());
-        let cl = goban.clone();
+        let cl = goban;
         let x = cl.get_liberties(s.coord);
 
         x.for_each(|coord| {
@@ -533,7 +573,7 @@ mod tests {
         g.display_goban();
         let score = g.calculate_score();
         assert_eq!(score, (10. * 19., 9. * 19.));
-        let mut goban: Goban = g.goban().clone();
+        let mut goban: Goban = *g.goban();
         goban.push_many(
             &{
                 let mut vec = vec![];
@@ -589,6 +629,90 @@ mod tests {
         )
     }
 
+    #[test]
+    fn ownership_estimate() {
+        let goban = Goban::new((9, 9));
+        assert!(goban.ownership_estimate().iter().all(|&x| x == 0.));
+        assert_eq!(goban.estimate_territories(), (0, 0));
+
+        let mut g = Game::new(GobanSizes::Nineteen, CHINESE);
+        for (x, y) in [(3, 3), (15, 15), (3, 15), (15, 3), (2, 9), (16, 9)] {
+            g.play(Move::Play(x, y));
+        }
+        // Middle game, no territory is closed yet.
+        assert_eq!(g.goban().calculate_territories(), (0, 0));
+        let ownership = g.goban().ownership_estimate();
+        assert!(ownership.iter().all(|&x| (-1. ..=1.).contains(&x)));
+        assert_eq!(ownership[3 * 19 + 3], 1.);
+        assert_eq!(ownership[15 * 19 + 15], -1.);
+        // The position is symmetric.
+        let (black, white) = g.goban().estimate_territories();
+        assert!(black > 0);
+        assert_eq!(black, white);
+        assert!(ownership[2 * 19 + 8] > 0.);
+        assert!(ownership[16 * 19 + 8] < 0.);
+    }
+
+    #[test]
+    fn notations() {
+        for size in [(19, 19), (9, 9), (13, 7)] {
+            for notation in [
+                Notation::Gtp,
+                Notation::Sgf,
+                Notation::Japanese,
+                Notation::Matrix,
+            ] {
+                for line in 0..size.0 {
+                    for column in 0..size.1 {
+                        let s = notation.format_coord(size, (line, column)).unwrap();
+                        assert_eq!(notation.parse_coord(size, &s), Ok((line, column)));
+                    }
+                }
+                for m in [Move::Pass, Move::Resign(Color::White), Move::Play(2, 5)] {
+                    let s = notation.format_move(size, m).unwrap();
+                    assert_eq!(notation.parse_move(size, &s), Ok(m));
+                }
+            }
+        }
+        let size = (19, 19);
+        // The I is skipped and the lines are counted from the bottom.
+        assert_eq!(Notation::Gtp.format_coord(size, (18, 8)).unwrap(), "J1");
+        assert_eq!(Notation::Gtp.format_coord(size, (0, 18)).unwrap(), "T19");
+        // The points outside the goban aren't formatted.
+        assert_eq!(Notation::Gtp.format_coord(size, (19, 0)), None);
+        assert_eq!(Notation::Japanese.format_coord((9, 9), (0, 9)), None);
+        assert_eq!(Notation::Gtp.format_coord((30, 30), (0, 29)), None);
+        assert_eq!(Notation::Sgf.format_move((30, 30), Move::Play(26, 0)), None);
+        assert_eq!(Notation::Gtp.parse_coord((9, 9), "c7"), Ok((2, 2)));
+        assert!(Notation::Gtp.parse_coord(size, "I5").is_err());
+        assert!(Notation::Gtp.parse_coord((9, 9), "K5").is_err());
+        assert!(Notation::Gtp.parse_coord((9, 9), "A10").is_err());
+        assert!(Notation::Gtp.parse_coord(size, "A0").is_err());
+        // The SGF points are (column, line).
+        assert_eq!(Notation::Sgf.parse_coord(size, "pd"), Ok((3, 15)));
+        assert_eq!(Notation::Sgf.parse_move(size, "tt"), Ok(Move::Pass));
+        assert!(Notation::Sgf.parse_coord((9, 9), "jj").is_err());
+        assert!(Notation::Sgf.parse_coord(size, "D4").is_err());
+        assert_eq!(Notation::Japanese.parse_coord(size, "4-四"), Ok((3, 15)));
+        assert_eq!(Notation::Japanese.parse_coord(size, "17-十六"), Ok((15, 2)));
+        assert_eq!(
+            Notation::Japanese.format_coord(size, (3, 15)).unwrap(),
+            "4-4"
+        );
+        assert!(Notation::Japanese.parse_coord(size, "20-1").is_err());
+        assert_eq!(Notation::Matrix.parse_coord(size, "1-19"), Ok((0, 18)));
+        assert!(Notation::Matrix.parse_coord(size, "0-1").is_err());
+
+        assert_eq!(Move::Play(15, 3).to_string(), "dp");
+        assert_eq!(Move::Pass.to_string(), "pass");
+        assert_eq!(Move::Play(30, 2).to_string(), "Play(30, 2)");
+        assert_eq!(Move::Resign(Color::Black).to_string(), "B+R");
+        for m in [Move::Play(15, 3), Move::Pass, Move::Resign(Color::Black)] {
+            assert_eq!(m.to_string().parse::<Move>(), Ok(m));
+        }
+        assert!("zz".parse::<Move>().is_err());
+    }
+
     #[test]
     fn zobrist_test() {
         let mut set = HashSet::new();
@@ -677,6 +801,97 @@ mod tests {
         assert!(game.try_play(Move::Play(0, 1)).is_err());
     }
 
+    #[test]
+    fn suicide_test_diagram() {
+        let game = game_from_diagram(
+            "
+            O . X .
+            O X . .
+            X . . .
+            . . . .
+            White to play
+            ",
+            CHINESE,
+        )
+        .unwrap();
+        assert_eq!(game.turn(), Color::White);
+        assert_eq!(game.check_point((0, 1)), Some(PlayError::Suicide));
+        assert!(!game.legals().any(|m| m == (0, 1)));
+    }
+
+    #[test]
+    fn goban_from_diagram() {
+        let game = Game::from_sgf(include_str!("../sgf/ShusakuvsInseki.sgf")).unwrap();
+        let goban: Goban = game.goban().pretty_string().parse().unwrap();
+        assert_eq!(&goban, game.goban());
+        assert_eq!(goban.to_vec(), game.goban().to_vec());
+
+        let labelled: Goban = "
+               A B C D E
+             5 . . . . . 5
+             4 . X O . . 4
+             3 . X O , . 3
+             2 . . . . . 2
+             1 . . . . . 1
+               A B C D E
+        "
+        .parse()
+        .unwrap();
+        let plain: Goban = "
+            .....
+            .●○..
+            .●○..
+            .....
+            .....
+        "
+        .parse()
+        .unwrap();
+        assert_eq!(labelled.size(), (5, 5));
+        assert_eq!(labelled, plain);
+        assert_eq!(labelled.get_color((2, 1)), Some(Color::Black));
+        assert_eq!(labelled.get_color((1, 2)), Some(Color::White));
+
+        let rectangle: Goban = "
+            X . O
+            . . .
+        "
+        .parse()
+        .unwrap();
+        assert_eq!(rectangle.size(), (2, 3));
+        assert_eq!(rectangle.get_color((0, 2)), Some(Color::White));
+
+        assert!("".parse::<Goban>().is_err());
+        assert!(". . .\n. .".parse::<Goban>().is_err());
+        assert!(". Z .".parse::<Goban>().is_err());
+        // The black stone doesn't have liberties.
+        assert!("X O\nO .".parse::<Goban>().is_err());
+        assert!(game_from_diagram("* . *", CHINESE).is_err());
+    }
+
+    #[test]
+    fn ko_from_diagram() {
+        let mut game = game_from_diagram(
+            "
+            . X O .
+            X * X O
+            . X O .
+            . . . .
+            White to play
+            ",
+            JAPANESE,
+        )
+        .unwrap();
+        assert!(game.check_ko(Stone {
+            coord: (1, 1),
+            color: Color::White,
+        }));
+        assert_eq!(game.try_play(Move::Play(1, 1)).err(), Some(PlayError::Ko));
+        game.play(Move::Play(3, 3)).play(Move::Play(3, 0));
+        game.play(Move::Play(1, 1));
+        assert_eq!(game.goban().get_color((1, 2)), EMPTY);
+        assert_eq!(game.prisoners(), (0, 1));
+    }
+
     #[test]
     fn sgf_test() {
         let game = Game::from_sgf(include_str!("../sgf/ShusakuvsInseki.sgf")).unwrap();
@@ -712,16 +927,1287 @@ mod tests {
     }
 
     #[test]
-    #[ignore]
-    #[cfg(deadstones)]
+    fn sgf_game_info() {
+        let game = Game::from_sgf(include_str!("../sgf/sgf_1.sgf")).unwrap();
+        let info = game.info();
+        assert_eq!(info.black_player.as_deref(), Some("Ding Hao"));
+        assert_eq!(info.white_player.as_deref(), Some("Shin Jinseo"));
+        assert_eq!(info.black_rank.as_deref(), Some("6p"));
+        assert_eq!(info.white_rank.as_deref(), Some("9p"));
+        assert_eq!(info.date.as_deref(), Some("2019-12-09"));
+        assert_eq!(info.source.as_deref(), Some("gokifu.com"));
+        assert!(info
+            .properties
+            .contains(&("LC".to_string(), "5".to_string())));
+
+        let exported = Game::from_sgf(&game.to_sgf()).unwrap();
+        assert_eq!(exported.info(), game.info());
+        assert_eq!(exported.goban(), game.goban());
+        assert_eq!(exported.prisoners(), game.prisoners());
+        assert_eq!(exported.outcome(), game.outcome());
+        assert_eq!(exported.moves(), game.moves());
+        assert_eq!(exported.clock(), game.clock());
+    }
+
+    #[test]
+    fn builder_game_info() {
+        let mut g = Game::builder()
+            .size((9, 9))
+            .info(GameInfo {
+                event: Some("Honinbo [final]".to_string()),
+                round: Some("3".to_string()),
+                ..Default::default()
+            })
+            .black_player("Shusaku")
+            .white_player("Inseki")
+            .build()
+            .unwrap();
+        g.play(Move::Play(2, 3)).play(Move::Pass);
+        g.info_mut().place = Some("Osaka".to_string());
+        let sgf = g.to_sgf();
+        assert!(sgf.contains("PB[Shusaku]PW[Inseki]"));
+        assert!(sgf.contains("EV[Honinbo [final\\]]RO[3]PC[Osaka]"));
+        assert!(sgf.contains(";B[dc];W[])"));
+        let imported = Game::from_sgf(&sgf).unwrap();
+        assert_eq!(imported.info(), g.info());
+        assert_eq!(imported.goban(), g.goban());
+
+        // The size and the handicap stones are written and read column first.
+        let g = Game::builder()
+            .size((9, 13))
+            .handicap(&[(2, 6), (6, 2)])
+            .build()
+            .unwrap();
+        let sgf = g.to_sgf();
+        assert!(sgf.contains("SZ[13:9]") && sgf.contains("AB[gc][cg]"));
+        let imported = Game::from_sgf(&sgf).unwrap();
+        assert_eq!(imported.size(), (9, 13));
+        assert_eq!(imported.goban(), g.goban());
+    }
+
+    #[test]
+    fn fixed_handicap() {
+        // D4 Q16 D16 Q4 D10 Q10 K4 K16 K10
+        assert_eq!(
+            fixed_handicap_points((19, 19), 9).unwrap(),
+            vec![
+                (15, 3),
+                (3, 15),
+                (3, 3),
+                (15, 15),
+                (9, 3),
+                (9, 15),
+                (15, 9),
+                (3, 9),
+                (9, 9)
+            ]
+        );
+        assert_eq!(
+            fixed_handicap_points((19, 19), 5).unwrap(),
+            vec![(15, 3), (3, 15), (3, 3), (15, 15), (9, 9)]
+        );
+        // C3 G7 C7
+        assert_eq!(
+            fixed_handicap_points((9, 9), 3).unwrap(),
+            vec![(6, 2), (2, 6), (2, 2)]
+        );
+        assert_eq!(
+            fixed_handicap_points((13, 13), 2).unwrap(),
+            vec![(9, 3), (3, 9)]
+        );
+        assert!(fixed_handicap_points((19, 19), 10).is_err());
+        assert!(fixed_handicap_points((19, 19), 1).is_err());
+        assert!(fixed_handicap_points((10, 10), 5).is_err());
+        assert!(fixed_handicap_points((7, 7), 4).is_ok());
+        assert!(fixed_handicap_points((5, 5), 2).is_err());
+
+        let mut g = Game::new(GobanSizes::Nineteen, CHINESE);
+        g.fixed_handicap(4).unwrap();
+        assert_eq!(g.handicap(), 4);
+        assert_eq!(g.turn(), Color::White);
+        assert_eq!(g.goban().get_color((3, 3)), Some(Color::Black));
+        g.play(Move::Pass).play(Move::Pass);
+        // Chinese rules give one point per handicap stone to White.
+        assert_eq!(g.calculate_score(), (361., CHINESE.komi + 4.));
+
+        let rule = Rule::new(CHINESE.komi, CHINESE.flag_illegal, CHINESE.flag_score);
+        assert_eq!(rule.handicap_compensation, HandicapCompensation::None);
+        assert_eq!(
+            rule.with_handicap_compensation(HandicapCompensation::PerStone),
+            CHINESE
+        );
+
+        let g = Game::builder()
+            .size((9, 9))
+            .rule(JAPANESE)
+            .fixed_handicap(2)
+            .build()
+            .unwrap();
+        assert_eq!(g.handicap_points(), &vec![(6, 2), (2, 6)]);
+        assert!(Game::builder()
+            .size((9, 9))
+            .fixed_handicap(9)
+            .build()
+            .is_ok());
+        assert!(Game::builder()
+            .size((9, 9))
+            .fixed_handicap(10)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn free_handicap() {
+        let points = free_handicap_points((19, 19), 13).unwrap();
+        assert_eq!(points.len(), 13);
+        assert_eq!(points[..9], fixed_handicap_points((19, 19), 9).unwrap()[..]);
+        assert!(points
+            .iter()
+            .all(|&(x, y)| (2..17).contains(&x) && (2..17).contains(&y)));
+        let unique = points.iter().collect::<HashSet<_>>();
+        assert_eq!(unique.len(), 13);
+        assert_eq!(points, free_handicap_points((19, 19), 13).unwrap());
+
+        assert_eq!(free_handicap_points((5, 5), 24).unwrap().len(), 24);
+        assert!(free_handicap_points((5, 5), 25).is_err());
+
+        let mut g = Game::new(GobanSizes::Nine, JAPANESE);
+        g.free_handicap(6).unwrap();
+        assert_eq!(g.goban().number_of_stones(), (6, 0));
+        g.play(Move::Pass).play(Move::Pass);
+        assert_eq!(g.calculate_score().1, JAPANESE.komi);
+        // The stones wouldn't be in the history after the first move.
+        assert!(g.free_handicap(2).is_err());
+        assert!(g.fixed_handicap(2).is_err());
+        assert_eq!(g.goban().number_of_stones(), (6, 0));
+        assert_eq!(g.position_at(2).unwrap().number_of_stones(), (6, 0));
+    }
+
+    #[test]
+    fn sgf_handicap() {
+        let g = Game::from_sgf("(;GM[1]SZ[19]HA[3];W[pp])").unwrap();
+        assert_eq!(g.handicap(), 3);
+        assert_eq!(g.handicap_points(), &vec![(15, 3), (3, 15), (3, 3)]);
+        assert_eq!(g.goban().get_color((15, 15)), Some(Color::White));
+
+        // AB[dp] is D4 on the bottom left.
+        let g = Game::from_sgf("(;GM[1]SZ[19]HA[2]AB[dp][pd];W[dd])").unwrap();
+        assert_eq!(g.handicap_points(), &vec![(15, 3), (3, 15)]);
+        assert_eq!(g.goban().get_color((15, 3)), Some(Color::Black));
+        let exported = Game::from_sgf(&g.to_sgf()).unwrap();
+        assert_eq!(exported.handicap_points(), g.handicap_points());
+        assert_eq!(exported.goban(), g.goban());
+    }
+
+    #[test]
+    #[cfg(feature = "deadstones")]
     fn dead_stones() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
         let game = Game::from_sgf(include_str!("../sgf/ShusakuvsInseki.sgf")).unwrap();
         game.display_goban();
-        let mut goban: Goban = game.goban().clone();
-        for string in game.dead_stones_wth_simulations(20) {
-            goban.remove_chain(string);
+        let dead_chains = game.dead_stones_wth_simulations(20, &mut StdRng::seed_from_u64(42));
+        let goban: &Goban = game.goban();
+        for chain in &dead_chains {
+            println!("{chain:?}");
+            let color = goban.get_stone_color(chain[0]);
+            assert!(chain
+                .iter()
+                .all(|&coord| goban.get_color(coord) == Some(color)));
+            assert_eq!(
+                chain.len(),
+                goban.get_chain_by_point(chain[0]).unwrap().num_stones as usize
+            );
+        }
+        assert!(!dead_chains.is_empty());
+        assert_eq!(
+            dead_chains,
+            game.dead_stones_wth_simulations(20, &mut StdRng::seed_from_u64(42))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "montecarlo")]
+    fn estimate_score() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        // White won by resignation, so the game has no score.
+        let game = Game::from_sgf(include_str!("../sgf/sgf_1.sgf")).unwrap();
+        let estimation = game.estimate_score(30, &mut StdRng::seed_from_u64(7));
+        println!(
+            "mean: {} std dev: {} black win probability: {}",
+            estimation.mean, estimation.std_dev, estimation.black_win_probability
+        );
+        assert_eq!(estimation.leader(), Some(Color::White));
+        assert!(estimation.mean < 0.);
+        assert!(estimation.std_dev >= 0.);
+        assert!((0. ..=1.).contains(&estimation.black_win_probability));
+        assert_eq!(estimation.ownership.len(), 19 * 19);
+        assert!(estimation.ownership.iter().all(|x| (-1. ..=1.).contains(x)));
+        assert_eq!(
+            estimation,
+            game.estimate_score(30, &mut StdRng::seed_from_u64(7))
+        );
+        assert_eq!(
+            game.estimate_score(0, &mut StdRng::seed_from_u64(7)),
+            game.estimate_score(1, &mut StdRng::seed_from_u64(7))
+        );
+    }
+
+    #[test]
+    fn clock_byo_yomi() {
+        let secs = Duration::from_secs;
+        let mut clock = Clock::new(TimeControl::ByoYomi {
+            main_time: secs(60),
+            period_time: secs(30),
+            periods: 3,
+        });
+        assert!(clock.spend(Color::Black, secs(50)));
+        assert!(!clock.player(Color::Black).in_overtime());
+        // 10s of main time then 25s in the first period.
+        assert!(clock.spend(Color::Black, secs(35)));
+        assert!(clock.player(Color::Black).in_overtime());
+        assert_eq!(clock.player(Color::Black).periods(), 3);
+        // Two periods are lost.
+        assert!(clock.spend(Color::Black, secs(80)));
+        assert_eq!(clock.player(Color::Black).periods(), 1);
+        assert_eq!(clock.to_sgf_time_left(Color::Black), "BL[30]OB[1]");
+        assert!(!clock.spend(Color::Black, secs(31)));
+        assert!(clock.player(Color::Black).flagged());
+        assert_eq!(clock.player(Color::White).main_time(), secs(60));
+    }
+
+    #[test]
+    fn clock_canadian_and_fischer() {
+        let secs = Duration::from_secs;
+        let mut clock = Clock::new(TimeControl::Canadian {
+            main_time: secs(10),
+            period_time: secs(60),
+            stones: 2,
+        });
+        assert!(clock.spend(Color::White, secs(30)));
+        assert_eq!(clock.player(Color::White).period_time(), secs(40));
+        assert_eq!(clock.to_gtp_time_left(Color::White), "white 40 1");
+        // The period is renewed after the stones.
+        assert!(clock.spend(Color::White, secs(40)));
+        assert_eq!(clock.player(Color::White).period_time(), secs(60));
+        assert_eq!(clock.player(Color::White).periods(), 2);
+        assert!(!clock.spend(Color::White, secs(61)));
+
+        let mut clock = Clock::new(TimeControl::Fischer {
+            main_time: secs(10),
+            increment: secs(5),
+        });
+        assert!(clock.spend(Color::Black, secs(8)));
+        assert_eq!(clock.player(Color::Black).main_time(), secs(7));
+        assert!(!clock.spend(Color::Black, secs(8)));
+    }
+
+    #[test]
+    fn time_control_notations() {
+        let secs = Duration::from_secs;
+        for time_control in [
+            TimeControl::Absolute {
+                main_time: secs(600),
+            },
+            TimeControl::Fischer {
+                main_time: secs(600),
+                increment: secs(10),
+            },
+            TimeControl::ByoYomi {
+                main_time: secs(600),
+                period_time: secs(30),
+                periods: 5,
+            },
+            TimeControl::Canadian {
+                main_time: secs(600),
+                period_time: secs(300),
+                stones: 25,
+            },
+        ] {
+            let sgf = time_control.to_sgf();
+            let (tm, ot) = sgf.split_once(']').unwrap();
+            let tm = tm.trim_start_matches("TM[").parse().unwrap();
+            let ot = ot.strip_prefix("OT[").map(|x| x.trim_end_matches(']'));
+            assert_eq!(TimeControl::from_sgf(tm, ot), Ok(time_control));
+        }
+        assert_eq!(
+            TimeControl::from_sgf(600, Some("5x30 byo-yomi"))
+                .unwrap()
+                .to_sgf(),
+            "TM[600]OT[5x30 byo-yomi]"
+        );
+        assert!(TimeControl::from_sgf(600, Some("sudden death?")).is_err());
+
+        assert_eq!(
+            TimeControl::from_gtp("600 300 25"),
+            Ok(Some(TimeControl::Canadian {
+                main_time: secs(600),
+                period_time: secs(300),
+                stones: 25,
+            }))
+        );
+        assert_eq!(
+            TimeControl::from_gtp("600 0 0"),
+            Ok(Some(TimeControl::Absolute {
+                main_time: secs(600)
+            }))
+        );
+        assert_eq!(TimeControl::from_gtp("0 1 0"), Ok(None));
+        assert!(TimeControl::from_gtp("600 0").is_err());
+
+        let mut clock = Clock::new(TimeControl::from_gtp("600 300 25").unwrap().unwrap());
+        clock.apply_gtp_time_left("b 120 7").unwrap();
+        assert_eq!(clock.to_gtp_time_left(Color::Black), "black 120 7");
+        clock.apply_gtp_time_left("white 500 0").unwrap();
+        assert_eq!(clock.to_sgf_time_left(Color::White), "WL[500]");
+        assert!(clock.apply_gtp_time_left("red 1 0").is_err());
+    }
+
+    #[test]
+    fn game_lost_on_time() {
+        let secs = Duration::from_secs;
+        let mut g = Game::builder()
+            .size((9, 9))
+            .time_control(TimeControl::ByoYomi {
+                main_time: secs(0),
+                period_time: secs(10),
+                periods: 1,
+            })
+            .build()
+            .unwrap();
+        g.play_with_time(Move::Play(4, 4), secs(5));
+        g.play_with_time(Move::Play(3, 3), secs(11));
+        assert_eq!(g.goban().get_color((3, 3)), EMPTY);
+        assert_eq!(g.outcome(), Some(EndGame::WinnerByTime(Color::Black)));
+
+        let sgf = "(;GM[1]SZ[9]TM[300]OT[3x30 byo-yomi];B[ee]BL[290];W[cc]WL[30]OW[2])";
+        let g = Game::from_sgf(sgf).unwrap();
+        let clock = g.clock().unwrap();
+        assert_eq!(clock.player(Color::Black).main_time(), secs(290));
+        assert!(clock.player(Color::White).in_overtime());
+        assert_eq!(clock.player(Color::White).periods(), 2);
+    }
+
+    #[test]
+    fn checked_play() {
+        let mut g = Game::new(GobanSizes::Nine, JAPANESE);
+        assert_eq!(
+            g.try_play(Move::Play(9, 0)).err(),
+            Some(PlayError::OutOfBounds)
+        );
+        assert_eq!(g.check_point((0, 20)), Some(PlayError::OutOfBounds));
+        assert_eq!(
+            g.try_play_as(Color::White, Move::Play(4, 4)).err(),
+            Some(PlayError::WrongTurn)
+        );
+        g.try_play_as(Color::Black, Move::Play(4, 4)).unwrap();
+
+        // The turn is restored when the move is illegal.
+        assert_eq!(
+            g.try_play_color(Color::Black, Move::Play(4, 4)).err(),
+            Some(PlayError::PointNotEmpty)
+        );
+        assert_eq!(g.turn(), Color::White);
+
+        g.try_play(Move::Pass)
+            .unwrap()
+            .try_play(Move::Pass)
+            .unwrap();
+        assert_eq!(g.try_play(Move::Pass).err(), Some(PlayError::GamePaused));
+        g.resume();
+        g.try_play(Move::Resign(Color::White)).unwrap();
+        assert_eq!(
+            g.try_play(Move::Play(0, 0)).err(),
+            Some(PlayError::GameOver)
+        );
+
+        let mut goban = Goban::new((9, 9));
+        assert_eq!(
+            goban.try_push((9, 9), Color::Black).err(),
+            Some(PlayError::OutOfBounds)
+        );
+        goban.try_push((1, 1), Color::Black).unwrap();
+        assert_eq!(
+            goban.try_push((1, 1), Color::White).err(),
+            Some(PlayError::PointNotEmpty)
+        );
+    }
+
+    #[test]
+    fn checked_sizes_and_rules() {
+        assert_eq!(GobanSizes::try_from(13), Ok(GobanSizes::Thirteen));
+        assert_eq!(GobanSizes::try_from(7), Ok(GobanSizes::Custom(7, 7)));
+        assert_eq!(GobanSizes::try_from(25), Err(PlayError::InvalidSize));
+        assert!(Goban::try_new((0, 9)).is_err());
+        assert_eq!(
+            Game::try_new(GobanSizes::Custom(300, 9), CHINESE).err(),
+            Some(PlayError::InvalidSize)
+        );
+        assert!(Game::builder().size((21, 21)).build().is_err());
+        assert!(Game::builder()
+            .size((9, 9))
+            .moves(&[Move::Play(2, 2), Move::Play(2, 2)])
+            .build()
+            .is_err());
+
+        assert!(Game::from_sgf("(;GM[1]SZ[9]RU[AGA];B[ee])").is_err());
+        assert!(Game::from_sgf("(;GM[1]SZ[9]RU[Japanese];B[ee])").is_ok());
+    }
+
+    #[test]
+    fn play_detailed() {
+        let mut g = game_from_diagram(
+            "
+            . X O .
+            X O . O
+            . X O .
+            . . . .
+            Black to play
+            ",
+            JAPANESE,
+        )
+        .unwrap();
+        let outcome = g.play_detailed(Move::Play(1, 2));
+        assert_eq!(outcome.captured, vec![vec![(1, 1)]]);
+        assert_eq!(outcome.ko_point, Some((1, 1)));
+        assert!(outcome.self_atari);
+        assert_eq!(outcome.ataris, vec![vec![(0, 2)]]);
+        assert_eq!(outcome.hash, g.goban().zobrist_hash());
+        assert_eq!(g.prisoners(), (1, 0));
+        assert_eq!(g.moves().last(), Some(&(Color::Black, Move::Play(1, 2))));
+
+        let outcome = g.play_detailed(Move::Play(3, 1));
+        assert!(outcome.captured.is_empty());
+        assert_eq!(outcome.ko_point, None);
+        assert!(!outcome.self_atari);
+        assert!(outcome.ataris.is_empty());
+
+        let outcome = g.play_detailed(Move::Pass);
+        assert_eq!(outcome.hash, g.goban().zobrist_hash());
+        assert_eq!(g.passes(), 1);
+    }
+
+    #[test]
+    fn game_events() {
+        let mut g = game_from_diagram(
+            "
+            . X O .
+            X O . O
+            . X O .
+            Black to play
+            ",
+            JAPANESE,
+        )
+        .unwrap();
+        let events = Arc::new(Mutex::new(vec![]));
+        let sink = events.clone();
+        let id = g.subscribe(move |event| sink.lock().unwrap().push(event.clone()));
+        g.play(Move::Play(1, 2));
+        g.try_play(Move::Play(1, 1)).unwrap_err();
+        g.play(Move::Pass).play(Move::Pass);
+        g.resume();
+        g.clone().play(Move::Play(2, 3));
+        g.play(Move::Resign(Color::Black));
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                GameEvent::StonePlaced(Stone {
+                    coord: (1, 2),
+                    color: Color::Black
+                }),
+                GameEvent::ChainsCaptured {
+                    color: Color::White,
+                    chains: vec![vec![(1, 1)]]
+                },
+                GameEvent::KoEstablished((1, 1)),
+                GameEvent::Pass(Color::White),
+                GameEvent::Pass(Color::Black),
+                GameEvent::ScoringPhase,
+                GameEvent::Resumed,
+                GameEvent::Resign(Color::Black),
+                GameEvent::GameOver(EndGame::WinnerByResign(Color::Black)),
+            ]
+        );
+        assert!(g.unsubscribe(id));
+        assert!(!g.unsubscribe(id));
+    }
+
+    #[test]
+    fn chain_views() {
+        let mut g = game_from_diagram(
+            "
+            . X O .
+            X O . O
+            . X O .
+            Black to play
+            ",
+            JAPANESE,
+        )
+        .unwrap();
+        g.play(Move::Play(1, 2));
+        let goban = g.goban();
+        // The captured white stone isn't listed.
+        assert_eq!(goban.chains().count(), 7);
+        assert!(goban.chains().all(|chain| chain.number_of_stones() == 1));
+        assert_eq!(goban.chain_at((1, 1)), None);
+        assert_eq!(goban.chain_at((5, 5)), None);
+
+        let white = goban.chain_at((0, 2)).unwrap();
+        assert_eq!(white.color(), Color::White);
+        assert!(white.is_atari());
+        assert_eq!(white.liberties().collect::<Vec<_>>(), vec![(0, 3)]);
+        let enemies: Vec<_> = white.adjacent_enemies().map(|c| c.origin()).collect();
+        assert_eq!(enemies, vec![(0, 1), (1, 2)]);
+
+        let black = goban.chain_at((1, 2)).unwrap();
+        assert_eq!(black.liberties().collect::<Vec<_>>(), vec![(1, 1)]);
+        assert_eq!(black.adjacent_enemies().count(), 3);
+        assert!(black.adjacent_enemies().all(|c| c.color() == Color::White));
+    }
+
+    #[test]
+    fn groups_and_cuts() {
+        let goban: Goban = "
+            X . X . . . .
+            . . . . . . .
+            . . . . . . .
+            . . . X O . .
+            . . . O X . .
+            . . . . . . .
+            . . . . . . .
+        "
+        .parse()
+        .unwrap();
+        let groups = goban.groups();
+        assert_eq!(groups.len(), 5);
+        assert_eq!(groups[0].stones, vec![(0, 0), (0, 2)]);
+        assert_eq!(groups[0].liberties, 4);
+        // The crosscut doesn't link the stones.
+        assert!(groups[1..].iter().all(|group| group.stones.len() == 1));
+        assert!(goban.cutting_points().is_empty());
+
+        let black = goban.chain_at((3, 3)).unwrap().index();
+        let adjacency = goban.chain_adjacency();
+        assert_eq!(adjacency.len(), 6);
+        assert_eq!(adjacency[&black].len(), 2);
+        assert!(adjacency[&goban.chain_at((0, 0)).unwrap().index()].is_empty());
+
+        let goban: Goban = "
+            . . . .
+            . X O .
+            . O X .
+            . . . X
+        "
+        .parse()
+        .unwrap();
+        let groups = goban.groups();
+        // Only the diagonal without opponent stones links the chains.
+        assert_eq!(groups.len(), 4);
+        assert_eq!(groups[3].stones, vec![(2, 2), (3, 3)]);
+        assert_eq!(groups[3].liberties, 2);
+    }
+
+    #[test]
+    fn ownership_map() {
+        use goban::pieces::territory::Ownership::*;
+
+        let goban: Goban = "
+            . O X . O X .
+            . O X . O X .
+        "
+        .parse()
+        .unwrap();
+        let ownership = goban.ownership_map(&[]);
+        assert_eq!(
+            &ownership[..7],
+            &[
+                Territory(Color::White),
+                Stone(Color::White),
+                Stone(Color::Black),
+                SekiLiberty,
+                Stone(Color::White),
+                Stone(Color::Black),
+                Territory(Color::Black),
+            ]
+        );
+
+        let goban: Goban = "
+            . X O . .
+            . X O . .
+            . X O O .
+            X X . O .
+        "
+        .parse()
+        .unwrap();
+        let ownership = goban.ownership_map(&[]);
+        assert_eq!(ownership[0], Territory(Color::Black));
+        assert_eq!(ownership[17], Dame);
+        assert_eq!(ownership[4], Territory(Color::White));
+
+        // A dead white stone in the black territory.
+        let goban: Goban = "
+            . X O .
+            O X O .
+            . X O .
+        "
+        .parse()
+        .unwrap();
+        let ownership = goban.ownership_map(&[(1, 0)]);
+        assert_eq!(ownership[4], Territory(Color::Black));
+        assert_eq!(ownership[0], Territory(Color::Black));
+        assert_eq!(goban.ownership_map(&[])[0], SekiLiberty);
+    }
+
+    #[test]
+    fn score_report() {
+        let diagram = "
+            . X O .
+            O X O .
+            . X O .
+        ";
+        let g = game_from_diagram(diagram, JAPANESE).unwrap();
+        let report = g.score_report(&[]);
+        let (black, white) = g.calculate_score();
+        assert_eq!(report.score, (black, white));
+
+        let report = g.score_report(&[(1, 0)]);
+        assert_eq!(report.territory, (3, 3));
+        assert_eq!(report.dead_stones, (0, 1));
+        assert_eq!(report.score, (4., 9.5));
+        assert_eq!(report.outcome(), EndGame::WinnerByScore(Color::White, 5.5));
+        assert_eq!(
+            report.to_string(),
+            "Scoring: territory
+                 Black   White
+Territory            3       3
+Prisoners            0       0
+Dead stones          1       0
+Komi                       6.5
+Total                4     9.5
+Result: W+5.5"
+        );
+
+        let g = game_from_diagram(diagram, CHINESE).unwrap();
+        let report = g.score_report(&[(1, 0)]);
+        assert_eq!(report.stones, (3, 3));
+        assert_eq!(report.score, (6., 13.5));
+        assert!(report.to_string().starts_with("Scoring: area"));
+        assert_eq!(report.margin(), -7.5);
+    }
+
+    #[test]
+    fn svg_rendering() {
+        assert_eq!(star_points((19, 19)).len(), 9);
+        assert_eq!(star_points((13, 13)).len(), 5);
+        assert_eq!(
+            star_points((9, 9)),
+            vec![(2, 2), (2, 6), (4, 4), (6, 2), (6, 6)]
+        );
+        assert!(star_points((5, 5)).is_empty());
+
+        let mut g = Game::new(GobanSizes::Custom(7, 11), JAPANESE);
+        g.play(Move::Play(1, 1)).play(Move::Play(5, 9));
+        let svg = SvgRenderer::new()
+            .coordinates(false)
+            .star_points(false)
+            .cell_size(10.)
+            .markup((3, 3), Markup::Triangle)
+            .markup((3, 4), Markup::Label("<a>".to_string()))
+            .markup((30, 4), Markup::Circle)
+            .render_game(&g);
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="120" height="80""#)
+        );
+        assert_eq!(svg.matches("<line").count(), 7 + 11);
+        // Two stones and the marker of the last move.
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert_eq!(svg.matches("<polygon").count(), 1);
+        assert!(svg.contains("&lt;a&gt;"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+
+        let svg = SvgRenderer::new()
+            .move_numbers(true)
+            .theme(Theme::print())
+            .render_game(&g);
+        assert!(svg.contains(">1</text>") && svg.contains(">2</text>"));
+        assert!(svg.contains(">A</text>") && svg.contains(">L</text>"));
+        assert!(!svg.contains(">I</text>"));
+
+        let goban: Goban = "
+            . X O .
+            . X O .
+        "
+        .parse()
+        .unwrap();
+        let svg = SvgRenderer::new().territory(&[]).render_goban(&goban);
+        assert_eq!(svg.matches("<rect").count(), 1 + 4);
+
+        let theme = Theme {
+            background: "red\" onload=\"alert(1)".to_string(),
+            black: "<script>".to_string(),
+            ..Theme::wood()
+        };
+        let svg = SvgRenderer::new().theme(theme).render_goban(&goban);
+        assert!(svg.contains("fill=\"red&quot; onload=&quot;alert(1)\""));
+        assert!(svg.contains("&lt;script&gt;"));
+        assert!(!svg.contains("<script>") && !svg.contains("\" onload"));
+    }
+
+    #[test]
+    fn terminal_rendering() {
+        let goban: Goban = "
+            . X O .
+            . X O .
+            . X O .
+        "
+        .parse()
+        .unwrap();
+        assert_eq!(
+            TerminalRenderer::new().render_goban(&goban),
+            "   ABCD
+ 3 ┏●○┓ 3
+ 2 ┠●○┨ 2
+ 1 ┗●○┛ 1
+   ABCD
+"
+        );
+        assert_eq!(
+            TerminalRenderer::new()
+                .coordinates(false)
+                .orientation(Orientation::White)
+                .territory(&[])
+                .render_goban(&goban),
+            "▫○●▪
+▫○●▪
+▫○●▪
+"
+        );
+        assert_eq!(
+            TerminalRenderer::new()
+                .coordinates(false)
+                .ascii(true)
+                .territory(&[])
+                .render_goban(&goban),
+            " b X O w 
+ b X O w 
+ b X O w 
+"
+        );
+        let colored = TerminalRenderer::new().ansi(true).render_goban(&goban);
+        assert!(colored.contains("\x1b[1;30;43m●"));
+        assert_eq!(colored.matches("\x1b[0m").count(), 3);
+
+        let mut g = Game::new(GobanSizes::Nine, JAPANESE);
+        g.play(Move::Play(4, 4));
+        let text = TerminalRenderer::new().render_game(&g);
+        assert!(text.contains(" 5 ┠┼┼┼◉┼┼┼┨ 5"));
+        assert!(text.contains(" 7 ┠┼╋┼┼┼╋┼┨ 7"));
+        let text = TerminalRenderer::new().last_move(false).render_game(&g);
+        assert!(text.contains(" 5 ┠┼┼┼●┼┼┼┨ 5"));
+    }
+
+    #[test]
+    fn kifu_figures() {
+        // A ko on the top edge: 5 captures 2, 8 retakes at 2 after a ko threat.
+        let mut g = Game::builder()
+            .size((5, 5))
+            .rule(JAPANESE)
+            .moves(&[
+                Move::Play(0, 1),
+                Move::Play(0, 2),
+                Move::Play(1, 2),
+                Move::Play(1, 3),
+                Move::Play(0, 3),
+                Move::Play(0, 4),
+            ])
+            .build()
+            .unwrap();
+        g.play(Move::Play(4, 4));
+        assert_eq!(g.goban().get_color((0, 2)), None);
+        g.play(Move::Play(0, 2));
+        g.play(Move::Pass);
+        g.play(Move::Resign(Color::White));
+
+        let numbers = g.move_numbers();
+        assert_eq!(numbers[2], Some(8));
+        assert_eq!(numbers[3], None);
+        assert_eq!(numbers[1], Some(1));
+
+        let figures = KifuPrinter::new().moves_per_figure(4).figures(&g);
+        assert_eq!(figures.len(), 3);
+        assert_eq!(figures[1].first_move, 5);
+        assert_eq!(figures[1].first_color, Color::Black);
+        assert_eq!(figures[1].goban.get_color((0, 2)), Some(Color::White));
+        assert_eq!(figures[1].notes, vec!["8 at C5"]);
+        assert_eq!(figures[2].last_move, 9);
+        assert_eq!(figures[2].first_color, Color::Black);
+        assert_eq!(figures[2].notes, vec!["9 pass"]);
+
+        let figures = KifuPrinter::new().figures(&g);
+        assert_eq!(figures.len(), 1);
+        assert_eq!(figures[0].notes, vec!["8 at 2", "9 pass"]);
+        let text = KifuPrinter::new().coordinates(false).render(&g);
+        assert_eq!(
+            text,
+            "Figure 1: moves 1 to 9, Black first
+ . 1 2 5 6
+ . . 3 4 .
+ . . . . .
+ . . . . .
+ . . . . 7
+8 at 2, 9 pass
+"
+        );
+        assert_eq!(KifuPrinter::new().render(&Game::default()), "");
+    }
+
+    #[test]
+    fn kifu_figures_recorded_colors() {
+        // The setup stones are kept and White plays first.
+        let mut g = game_from_diagram(
+            "
+            . . . . .
+            . X . . .
+            . . . . .
+            . . . O .
+            . . . . .
+            White to play
+            ",
+            CHINESE,
+        )
+        .unwrap();
+        g.play(Move::Play(0, 0));
+        // Black plays twice in a row.
+        g.play(Move::Play(0, 4));
+        g.try_play_color(Color::Black, Move::Play(4, 0)).unwrap();
+        g.play(Move::Play(4, 4));
+
+        let figures = KifuPrinter::new().moves_per_figure(2).figures(&g);
+        assert_eq!(figures.len(), 2);
+        assert_eq!(figures[0].first_color, Color::White);
+        assert_eq!(figures[0].goban.get_color((1, 1)), Some(Color::Black));
+        assert_eq!(figures[0].goban.get_color((3, 3)), Some(Color::White));
+        assert_eq!(figures[1].first_color, Color::Black);
+        assert_eq!(figures[1].goban.get_color((0, 0)), Some(Color::White));
+        assert_eq!(figures[1].goban.get_color((0, 4)), Some(Color::Black));
+        assert_eq!(figures[1].goban.get_color((1, 1)), Some(Color::Black));
+        assert_eq!(figures[1].goban, g.position_at(2).unwrap());
+    }
+
+    #[test]
+    fn sensei_diagrams() {
+        let goban: Goban = "
+            . X O . .
+            . X O . .
+            . . . . .
+            . . . . .
+            . . . . .
+        "
+        .parse()
+        .unwrap();
+        assert_eq!(
+            goban.to_sensei(),
+            "$$B5
+$$ +-----------+
+$$ | . X O . . |
+$$ | . X O . . |
+$$ | . . . . . |
+$$ | . . . . . |
+$$ | . . . . . |
+$$ +-----------+
+"
+        );
+        assert_eq!(Goban::from_sensei(&goban.to_sensei()).unwrap(), goban);
+
+        // The marked stones, the marks and the labels of the empty points.
+        let goban = Goban::from_sensei(
+            "$$ Bottom right corner
+             $$ . B W C a |
+             $$ . # @ S b |
+             $$ . Y Q T , |
+             $$ . Z P M _ |
+             $$ ----------+",
+        )
+        .unwrap();
+        assert_eq!(goban.size(), (19, 19));
+        assert_eq!(goban.get_color((15, 15)), Some(Color::Black));
+        assert_eq!(goban.get_color((18, 16)), Some(Color::White));
+        assert_eq!(goban.number_of_stones(), (4, 4));
+
+        // The ko: 5 captures 2, 8 retakes at 2.
+        let mut g = Game::new(GobanSizes::Custom(5, 5), JAPANESE);
+        for m in [
+            (0, 1),
+            (0, 2),
+            (1, 2),
+            (1, 3),
+            (0, 3),
+            (0, 4),
+            (4, 4),
+            (0, 2),
+        ] {
+            g.play(Move::Play(m.0, m.1));
+        }
+        g.play(Move::Pass);
+        let sensei = g.to_sensei(10);
+        assert_eq!(
+            sensei,
+            "$$B5
+$$ +-----------+
+$$ | . 1 2 5 6 |
+$$ | . . 3 4 . |
+$$ | . . . . . |
+$$ | . . . . . |
+$$ | . . . . 7 |
+$$ +-----------+
+8 at 2, 9 pass
+"
+        );
+        let copy = Game::from_sensei(&sensei, JAPANESE).unwrap();
+        assert_eq!(copy.moves(), g.moves());
+        assert_eq!(copy.goban(), g.goban());
+
+        let sensei = g.to_sensei(5);
+        assert!(sensei.starts_with("$$B5m5\n"));
+        assert!(sensei.contains("$$ | . X O 1 2 |"));
+        assert!(sensei.ends_with("8 at C5, 9 pass\n"));
+        let copy = Game::from_sensei(&sensei, JAPANESE).unwrap();
+        assert_eq!(copy.goban(), g.goban());
+        assert_eq!(copy.turn(), g.turn());
+
+        // The stones set up before the first move are under the numbered moves.
+        let mut g = game_from_diagram(
+            "
+            . . .
+            . X .
+            . . .
+            ",
+            JAPANESE,
+        )
+        .unwrap();
+        g.play(Move::Play(0, 0)).play(Move::Play(2, 2));
+        let sensei = g.to_sensei(10);
+        assert!(sensei.contains("$$ | 1 . . |\n$$ | . X . |\n$$ | . . 2 |"));
+
+        assert!(Game::from_sensei("$$B\n$$ | X 1 |\n$$ | 1 . |", JAPANESE).is_err());
+        assert!(Goban::from_sensei(". X O").is_err());
+        assert!(Goban::from_sensei("$$B\n$$ | X ? |").is_err());
+    }
+
+    #[test]
+    fn history_positions() {
+        let mut g = Game::new(GobanSizes::Nine, CHINESE);
+        let mut positions = vec![*g.goban()];
+        let mut rng = StdRng::seed_from_u64(42);
+        while !g.is_over() && g.moves().len() < 150 {
+            let legals = g.legals().collect::<Vec<_>>();
+            let m = legals
+                .choose(&mut rng)
+                .map_or(Move::Pass, |&point| point.into());
+            g.play(m);
+            positions.push(*g.goban());
+        }
+        assert!(g.prisoners().0 + g.prisoners().1 > 0);
+
+        for (number, position) in positions.iter().enumerate() {
+            let rebuilt = g.position_at(number).unwrap();
+            assert_eq!(&rebuilt, position, "position {number}");
+            assert_eq!(rebuilt.to_vec(), position.to_vec(), "position {number}");
+        }
+        assert_eq!(g.position_at(positions.len()), None);
+        assert_eq!(g.history().len(), positions.len());
+        assert!(g.history().eq(positions.iter().cloned()));
+        assert!(g.history().rev().eq(positions.iter().rev().cloned()));
+
+        // Both ends meet in the middle.
+        let mut history = g.history().skip(40);
+        assert_eq!(history.next_back().as_ref(), positions.last());
+        assert_eq!(history.next().as_ref(), positions.get(40));
+        assert_eq!(history.len(), positions.len() - 42);
+
+        // The goban set up before the first move is the first position.
+        let mut g = Game::builder()
+            .size((9, 9))
+            .handicap(&[(2, 2), (6, 6)])
+            .moves(&[Move::Play(4, 4), Move::Pass])
+            .build()
+            .unwrap();
+        g.play(Move::Resign(Color::Black));
+        assert_eq!(g.position_at(0).unwrap().number_of_stones(), (2, 0));
+        assert_eq!(g.position_at(3).as_ref(), Some(g.goban()));
+        assert_eq!(Game::default().history().count(), 1);
+    }
+
+    #[test]
+    fn chain_slots_are_reused() {
+        // Random moves with the eyes filled, the chains are captured again and again.
+        let mut g = Game::new(GobanSizes::Nine, JAPANESE);
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut captures = 0;
+        for number in 0..30_000 {
+            let point = (rng.gen_range(0..9), rng.gen_range(0..9));
+            let m = if g.check_point(point).is_none() {
+                Move::Play(point.0, point.1)
+            } else {
+                let legals = g.legals().collect::<Vec<_>>();
+                legals
+                    .choose(&mut rng)
+                    .map_or(Move::Pass, |&point| point.into())
+            };
+            if g.passes() >= 2 {
+                g.resume();
+            }
+            let prisoners = g.prisoners();
+            g.play(m);
+            if g.prisoners() != prisoners {
+                captures += 1;
+            }
+            assert!(g.goban().chain_slots() <= 81, "move {number}");
+
+            if number % 1000 == 0 {
+                // The same position rebuilt from scratch has the same chains.
+                let mut goban = Goban::new((9, 9));
+                for stone in g.goban().get_stones() {
+                    goban.push_stone(stone);
+                }
+                assert_eq!(&goban, g.goban());
+                let chains = |goban: &Goban| {
+                    let mut chains = goban
+                        .chains()
+                        .map(|chain| {
+                            let mut stones = chain.stones().collect::<Vec<_>>();
+                            stones.sort_unstable();
+                            (stones, chain.liberties().collect::<Vec<_>>())
+                        })
+                        .collect::<Vec<_>>();
+                    chains.sort_unstable();
+                    chains
+                };
+                assert_eq!(chains(&goban), chains(g.goban()), "move {number}");
+            }
+        }
+        assert!(captures > 1000);
+    }
+
+    #[test]
+    fn apply_and_revert() {
+        let mut goban = Goban::new((9, 9));
+        let mut rng = StdRng::seed_from_u64(11);
+        let state = |goban: &Goban| {
+            let mut chains = goban
+                .chains()
+                .map(|chain| {
+                    let mut stones = chain.stones().collect::<Vec<_>>();
+                    stones.sort_unstable();
+                    (stones, chain.liberties().collect::<Vec<_>>())
+                })
+                .collect::<Vec<_>>();
+            chains.sort_unstable();
+            (
+                goban.to_vec(),
+                goban.zobrist_hash(),
+                goban.chain_slots(),
+                chains,
+            )
+        };
+        let mut stack = vec![];
+        let mut color = Color::Black;
+        let mut captures = 0;
+        for number in 0..20_000 {
+            if stack.len() < 60 && rng.gen_range(0..4) != 0 {
+                let empties = goban.get_empty_coords().collect::<Vec<_>>();
+                let point = *empties.choose(&mut rng).unwrap();
+                let before = state(&goban);
+                let hash = goban.hash_after(point, color);
+                let undo = goban.apply(point, color);
+                if goban.get_chain_by_point(point).unwrap().is_dead() {
+                    // Suicide, taken back at once.
+                    goban.revert(undo);
+                    assert_eq!(state(&goban), before, "move {number}");
+                    continue;
+                }
+                assert_eq!(goban.zobrist_hash(), hash, "move {number}");
+                assert_eq!(undo.previous_hash(), before.1);
+                if undo.captured_stones() > 0 {
+                    captures += 1;
+                }
+                stack.push((before, undo));
+                color = !color;
+            } else if let Some((before, undo)) = stack.pop() {
+                goban.revert(undo);
+                assert_eq!(state(&goban), before, "move {number}");
+                color = !color;
+            }
+        }
+        assert!(captures > 100);
+    }
+
+    #[cfg(feature = "bitboard")]
+    #[test]
+    fn bitboard_goban() {
+        use goban::pieces::bitboard::BitGoban;
+
+        for size in [(9, 9), (19, 19), (5, 13)] {
+            let mut goban = Goban::new(size);
+            let mut bit_goban = BitGoban::new(size);
+            let mut rng = StdRng::seed_from_u64(3);
+            let length = size.0 as usize * size.1 as usize;
+            let mut stack = vec![];
+            let mut color = Color::Black;
+            let mut captures = 0;
+            for number in 0..5000 {
+                if stack.len() < 200 && rng.gen_range(0..5) != 0 {
+                    let empties = goban.get_empty_coords().collect::<Vec<_>>();
+                    let point = *empties.choose(&mut rng).unwrap();
+                    let undo = goban.apply(point, color);
+                    let bit_undo = bit_goban.apply(point, color);
+                    assert_eq!(undo.captured_stones(), bit_undo.captured_stones());
+                    captures += (undo.captured_stones() > 0) as u32;
+                    if goban.get_chain_by_point(point).unwrap().is_dead() {
+                        goban.revert(undo);
+                        bit_goban.revert(bit_undo);
+                    } else {
+                        stack.push((undo, bit_undo));
+                        color = !color;
+                    }
+                } else if let Some((undo, bit_undo)) = stack.pop() {
+                    goban.revert(undo);
+                    bit_goban.revert(bit_undo);
+                    color = !color;
+                }
+                assert_eq!(
+                    bit_goban.to_vec(),
+                    goban.to_vec()[..length],
+                    "move {number}"
+                );
+                assert_eq!(bit_goban.zobrist_hash(), goban.zobrist_hash());
+                assert_eq!(bit_goban.number_of_stones(), goban.number_of_stones());
+                if number % 50 == 0 {
+                    assert_eq!(
+                        bit_goban.calculate_territories(),
+                        goban.calculate_territories()
+                    );
+                    let point = (rng.gen_range(0..size.0), rng.gen_range(0..size.1));
+                    if let Some(chain) = goban.get_chain_by_point(point) {
+                        let bit_chain = bit_goban.chain(point);
+                        assert_eq!(bit_chain.count(), chain.num_stones as u32);
+                        assert_eq!(
+                            bit_goban.liberties(bit_chain).count() as usize,
+                            chain.number_of_liberties()
+                        );
+                    }
+                }
+            }
+            assert!(captures > 50, "{captures} captures on {size:?}");
+            assert_eq!(BitGoban::from(&goban), bit_goban);
+            assert_eq!(Goban::from(&bit_goban), goban);
+            assert_eq!(bit_goban.to_string(), goban.to_string());
+        }
+    }
+
+    #[test]
+    fn game_convert() {
+        let mut game = Game::new(GobanSizes::Nine, CHINESE);
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..60 {
+            let legals = game.legals().collect::<Vec<_>>();
+            let Some(&point) = legals.choose(&mut rng) else {
+                break;
+            };
+            game.play(Move::Play(point.0, point.1));
+        }
+        let copy: Game = game.convert();
+        assert_eq!(copy.goban(), game.goban());
+        assert_eq!(copy.turn(), game.turn());
+        assert_eq!(copy.prisoners(), game.prisoners());
+        assert_eq!(
+            copy.legals().collect::<Vec<_>>(),
+            game.legals().collect::<Vec<_>>()
+        );
+
+        let with_board = Game::with_board(Goban::new((9, 9)), CHINESE);
+        assert_eq!(
+            with_board.goban(),
+            Game::new(GobanSizes::Nine, CHINESE).goban()
+        );
+    }
+
+    #[cfg(feature = "bitboard")]
+    #[test]
+    fn game_on_bitboard() {
+        use goban::pieces::bitboard::BitGoban;
+
+        for (size, rule) in [((9, 9), CHINESE), ((19, 19), JAPANESE), ((7, 11), CHINESE)] {
+            let mut game = Game::new(GobanSizes::Custom(size.0, size.1), rule);
+            let mut bit_game = Game::with_board(BitGoban::new((size.0 as u8, size.1 as u8)), rule);
+            let mut rng = StdRng::seed_from_u64(11);
+            while !game.is_over() {
+                let legals = game.legals().collect::<Vec<_>>();
+                assert_eq!(bit_game.legals().collect::<Vec<_>>(), legals);
+                let play = match legals.choose(&mut rng) {
+                    Some(&point) if rng.gen_range(0..50) != 0 => Move::Play(point.0, point.1),
+                    _ => Move::Pass,
+                };
+                game.play(play);
+                bit_game.play(play);
+                assert_eq!(bit_game.goban().zobrist_hash(), game.goban().zobrist_hash());
+                assert_eq!(bit_game.prisoners(), game.prisoners());
+                assert_eq!(bit_game.turn(), game.turn());
+            }
+            assert!(bit_game.is_over());
+            assert_eq!(bit_game.calculate_score(), game.calculate_score());
+            assert_eq!(Goban::from(bit_game.goban()), *game.goban());
+            assert_eq!(game.convert::<BitGoban>().goban(), bit_game.goban());
+        }
+    }
+
+    #[cfg(feature = "bitboard")]
+    #[test]
+    fn score_report_on_bitboard() {
+        use goban::pieces::bitboard::BitGoban;
+
+        let diagram = "
+            . X O .
+            O X O .
+            . X O .
+        ";
+        for rule in [JAPANESE, CHINESE] {
+            let game = game_from_diagram(diagram, rule).unwrap();
+            let bit_game: Game<BitGoban> = game.convert();
+            assert_eq!(bit_game.score_report(&[]), game.score_report(&[]));
+            let report = bit_game.score_report(&[(1, 0)]);
+            assert_eq!(report, game.score_report(&[(1, 0)]));
+            assert_eq!(report.dead_stones, (0, 1));
+            assert_eq!(bit_game.to_sensei(0), game.to_sensei(0));
+        }
+    }
+
+    #[test]
+    fn goban_clones() {
+        let mut goban: Goban = "
+            . X O .
+            X O . .
+            . X O .
+            . . . .
+        "
+        .parse()
+        .unwrap();
+        let copy = goban;
+        let mut reused = Goban::new((19, 19));
+        reused.clone_from(&goban);
+        goban.push((1, 2), Color::Black);
+        goban.push((0, 3), Color::Black);
+        for other in [&copy, &reused] {
+            assert_eq!(other.size(), (4, 4));
+            assert_ne!(other, &goban);
+            assert_eq!(other.get_color((1, 2)), None);
+            assert_eq!(other.chains().count(), 6);
+            assert_eq!(other.chain_at((1, 1)).unwrap().number_of_liberties(), 1);
         }
-        println!("{}", goban);
+        assert_eq!(goban.chain_at((1, 1)).unwrap().number_of_liberties(), 0);
     }
 
     #[test]